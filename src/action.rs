@@ -1,23 +1,50 @@
+use crossterm::event::MouseEvent;
+
+use crate::app::View;
+use crate::keymap::Key;
+
 /// Actions that can be performed in the application
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum Action {
-    /// Start a new pomodoro session
-    Start,
-    /// Pause the current timer
-    Pause,
-    /// Resume a paused timer
-    Resume,
-    /// Stop/cancel the current session
-    Stop,
     /// Quit the application
     Quit,
+    /// Pause or resume the work timer
+    TogglePause,
+    /// Stop the timer, saving the session as interrupted
+    Stop,
+    /// Skip the rest of a break, or the break itself
+    Skip,
+    /// Start a break after a work session
+    StartBreak,
+    /// Start another pomodoro on the last task
+    Repeat,
+    /// Mark the task of the finished session done
+    Done,
+    /// Open the retrospective for the finished session
+    Notes,
     /// Timer tick (internal action)
     Tick,
     /// Character input for task description
     Input(char),
-    /// Delete last character
+    /// Delete the character before the cursor
     Backspace,
+    /// Delete the character under the cursor
+    Delete,
+    /// Delete the word before the cursor
+    DeleteWord,
+    /// Delete everything before the cursor
+    DeleteToStart,
+    /// Move the cursor left (or switch to the previous tab outside text input)
+    CursorLeft,
+    /// Move the cursor right (or switch to the next tab outside text input)
+    CursorRight,
+    /// Move to the start of the line or list
+    Home,
+    /// Move to the end of the line or list
+    End,
+    /// Text pasted into the terminal
+    Paste(String),
     /// Confirm input
     Confirm,
     /// Cancel input
@@ -28,10 +55,62 @@ pub enum Action {
     PrevTab,
     /// Cycle through session filters
     CycleFilter,
+    /// Start searching the history
+    Search,
+    /// Sort the history by the next column
+    Sort,
+    /// Reverse the history sort order
+    ReverseSort,
+    /// Toggle grouping the history by day
+    Group,
+    /// Open the jump-to-date prompt
+    JumpToDate,
+    /// Edit the task of the selected session
+    EditTask,
+    /// Edit the notes of the selected session
+    EditNotes,
+    /// Delete the selected session or planned task
+    DeleteSelected,
+    /// Add a planned task
+    AddTask,
+    /// Add a pomodoro to the selected task's estimate
+    IncreaseEstimate,
+    /// Take a pomodoro off the selected task's estimate
+    DecreaseEstimate,
+    /// Mark the selected planned task done or not done
+    ToggleDone,
+    /// Move the selected planned task up
+    MoveUp,
+    /// Move the selected planned task down
+    MoveDown,
+    /// Move the selected planned task to tomorrow
+    CarryOver,
+    /// Toggle the stats heatmap
+    Heatmap,
+    /// Toggle the stats distribution panel
+    Distribution,
+    /// Toggle the stats estimation accuracy panel
+    Estimates,
     /// Scroll up in list views
     ScrollUp,
     /// Scroll down in list views
     ScrollDown,
+    /// Scroll up a page in list views
+    PageUp,
+    /// Scroll down a page in list views
+    PageDown,
+    /// Show the keys for the current view
+    ShowHelp,
+    /// Open the command palette
+    OpenPalette,
+    /// A key press, resolved by the app through the keymap
+    Key(Key),
+    /// A click or scroll, resolved by the app against the regions of the last frame
+    Mouse(MouseEvent),
+    /// Switch to a view
+    ShowView(View),
+    /// Select a row of the current list, or open it when already selected
+    SelectRow(usize),
     /// No action
     None,
 }
@@ -1,12 +1,67 @@
-use std::time::Instant;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use chrono::{Datelike, Local, NaiveDate, Utc};
-use color_eyre::eyre::Result;
+use chrono::{Datelike, Local, NaiveDate, Timelike, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::{bail, eyre, Result};
+use crossterm::event::{MouseEvent, MouseEventKind};
 
 use crate::action::Action;
+use crate::chart::{ChartScale, FocusChart};
 use crate::components::session_list::SessionFilter;
-use crate::session::Session;
+use crate::config::Profile;
+use crate::distribution::Distribution;
+use crate::editor::LineEditor;
+use crate::estimate::EstimateReport;
+use crate::export::{export_to_file, ExportFormat, ExportOptions, Timestamps, DEFAULT_COLUMNS};
+use crate::goal::{self, DailyGoal, GoalTarget, Streaks, DEFAULT_POMODORO_SECS};
+use crate::heatmap::Heatmap;
+use crate::integrations::{Integrations, TaskwarriorTask, TodoItem};
+use crate::keymap::{Context, Key, Keymap};
+use crate::mouse::MouseTargets;
+use crate::palette::{self, PaletteCommand};
+use crate::parse::{parse_day, parse_duration, split_estimate};
+use crate::plan::{Plan, PlanTask};
+use crate::session::{Pause, Session};
 use crate::storage::Storage;
+use crate::suggest::{self, PastTask};
+
+/// Maximum number of autocomplete suggestions offered
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Rows moved by PageUp/PageDown in the history view
+const HISTORY_PAGE: usize = 10;
+
+/// How long pending Taskwarrior tasks are reused before `task export` runs again
+const TASKWARRIOR_REFRESH: Duration = Duration::from_secs(60);
+
+/// Result of a tracker call run off the event loop, so a slow `task` or `timew` can't freeze the UI
+enum TrackerReply {
+    /// Pending tasks from `task export`
+    PendingTasks(Result<Vec<TaskwarriorTask>>),
+    /// Errors from reporting a completed pomodoro
+    Synced(Vec<String>),
+}
+
+/// A completion offered while entering a task
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    /// Task description filled in when accepted
+    pub text: String,
+}
+
+/// Progress through the suggestions while cycling with Tab
+#[derive(Debug, Clone)]
+pub struct Completion {
+    /// Text typed before the first Tab, which the suggestions are for
+    pub query: String,
+    /// Index of the accepted suggestion
+    pub selected: usize,
+}
 
 /// What kind of timer is currently active
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -34,11 +89,148 @@ pub enum AppState {
     BreakFinished,
 }
 
+/// Field of the retrospective form being edited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetroField {
+    #[default]
+    Accomplished,
+    Focus,
+    Notes,
+}
+
+impl RetroField {
+    fn next(self) -> Self {
+        match self {
+            RetroField::Accomplished => RetroField::Focus,
+            RetroField::Focus => RetroField::Notes,
+            RetroField::Notes => RetroField::Accomplished,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            RetroField::Accomplished => RetroField::Notes,
+            RetroField::Focus => RetroField::Accomplished,
+            RetroField::Notes => RetroField::Focus,
+        }
+    }
+}
+
+/// Quick retrospective filled in after a work session
+#[derive(Debug, Clone, Default)]
+pub struct RetroForm {
+    /// Session the answers are saved to
+    pub session_id: uuid::Uuid,
+    pub field: RetroField,
+    pub accomplished: LineEditor,
+    pub focus: Option<u8>,
+    pub notes: LineEditor,
+}
+
+/// Field of a past session being edited from the history view
+#[derive(Debug, Clone)]
+pub enum SessionEdit {
+    Task(LineEditor),
+    Notes(LineEditor),
+}
+
+impl SessionEdit {
+    /// Editor holding the new value
+    pub fn editor(&self) -> &LineEditor {
+        match self {
+            SessionEdit::Task(editor) | SessionEdit::Notes(editor) => editor,
+        }
+    }
+
+    fn editor_mut(&mut self) -> &mut LineEditor {
+        match self {
+            SessionEdit::Task(editor) | SessionEdit::Notes(editor) => editor,
+        }
+    }
+}
+
+/// Column the history view is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistorySort {
+    #[default]
+    Date,
+    Duration,
+    Status,
+    Task,
+}
+
+impl HistorySort {
+    fn next(self) -> Self {
+        match self {
+            HistorySort::Date => HistorySort::Duration,
+            HistorySort::Duration => HistorySort::Status,
+            HistorySort::Status => HistorySort::Task,
+            HistorySort::Task => HistorySort::Date,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistorySort::Date => "Date",
+            HistorySort::Duration => "Duration",
+            HistorySort::Status => "Status",
+            HistorySort::Task => "Task",
+        }
+    }
+}
+
+/// A line of the history table
+#[derive(Debug, Clone)]
+pub enum HistoryRow<'a> {
+    /// Heading for a day, with all of that day's matching sessions
+    Day { date: NaiveDate, sessions: Vec<&'a Session> },
+    Session(&'a Session),
+}
+
+impl HistoryRow<'_> {
+    /// Local date the row belongs to
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            HistoryRow::Day { date, .. } => *date,
+            HistoryRow::Session(session) => session.started_at.with_timezone(&Local).date_naive(),
+        }
+    }
+}
+
+/// Prompt for a date to jump to in the history view
+#[derive(Debug, Clone, Default)]
+pub struct DateJump {
+    pub editor: LineEditor,
+    /// Whether the last submitted text failed to parse
+    pub invalid: bool,
+}
+
+/// Command palette input, while open
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    pub editor: LineEditor,
+    /// Index of the highlighted match
+    pub selected: usize,
+    /// Why the last command could not run
+    pub error: Option<String>,
+}
+
+/// Panel shown below the stat cards in the stats view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsPanel {
+    #[default]
+    Overview,
+    Heatmap,
+    Distribution,
+    Estimates,
+}
+
 /// The current view/tab being displayed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum View {
     #[default]
     Timer,
+    Plan,
     History,
     Stats,
 }
@@ -48,15 +240,17 @@ impl View {
     pub fn index(&self) -> usize {
         match self {
             View::Timer => 0,
-            View::History => 1,
-            View::Stats => 2,
+            View::Plan => 1,
+            View::History => 2,
+            View::Stats => 3,
         }
     }
 
     /// Get next view (wrapping)
     pub fn next(&self) -> Self {
         match self {
-            View::Timer => View::History,
+            View::Timer => View::Plan,
+            View::Plan => View::History,
             View::History => View::Stats,
             View::Stats => View::Timer,
         }
@@ -66,7 +260,8 @@ impl View {
     pub fn prev(&self) -> Self {
         match self {
             View::Timer => View::Stats,
-            View::History => View::Timer,
+            View::Plan => View::Timer,
+            View::History => View::Plan,
             View::Stats => View::History,
         }
     }
@@ -84,10 +279,14 @@ pub struct App {
     pub remaining_secs: u32,
     /// Current task description
     pub task_description: String,
+    /// Text being typed while entering a task
+    pub task_input: LineEditor,
     /// When the current session started
     session_start: Option<Instant>,
     /// Time when paused (for calculating elapsed time)
     pause_start: Option<Instant>,
+    /// Pauses taken so far in the current work session
+    pauses: Vec<Pause>,
     /// Storage for persistence
     storage: Storage,
     /// When the pomodoro was started (for session record)
@@ -100,6 +299,34 @@ pub struct App {
     pub sessions_cache: Vec<Session>,
     /// Currently selected row in history view
     pub history_selected: usize,
+    /// Whether the detail pane for the selected session is open
+    pub history_detail: bool,
+    /// Edit in progress on the selected session
+    pub history_edit: Option<SessionEdit>,
+    /// Whether deleting the selected session is waiting for confirmation
+    pub history_confirm_delete: bool,
+    /// Search narrowing the history view to matching sessions
+    pub history_search: LineEditor,
+    /// Whether the search query is being typed
+    pub history_searching: bool,
+    /// Column the history view is sorted by
+    pub history_sort: HistorySort,
+    /// Whether the sort order is reversed from newest, longest, completed or A-Z first
+    pub history_sort_ascending: bool,
+    /// Whether history is grouped under day headings
+    pub history_grouped: bool,
+    /// Days whose sessions are hidden under their heading
+    pub history_collapsed: HashSet<NaiveDate>,
+    /// Jump-to-date prompt, while open
+    pub history_jump: Option<DateJump>,
+    /// Panel shown in the stats view
+    pub stats_panel: StatsPanel,
+    /// Day under the cursor in the heatmap
+    pub heatmap_selected: NaiveDate,
+    /// How many periods back the stats chart is scrolled
+    pub stats_chart_offset: u32,
+    /// Daily focus goal from the config file
+    pub goal: DailyGoal,
     /// Current timer mode (work, short break, long break)
     pub timer_mode: TimerMode,
     /// Original work duration in seconds
@@ -112,6 +339,62 @@ pub struct App {
     pub sessions_until_long_break: u32,
     /// Work sessions completed since last long break
     pub work_sessions_completed: u32,
+    /// External task/time trackers to sync with
+    pub integrations: Integrations,
+    /// Pending Taskwarrior tasks offered while entering a task
+    pub taskwarrior_tasks: Vec<TaskwarriorTask>,
+    /// Selected row among the matching Taskwarrior tasks
+    pub taskwarrior_selected: Option<usize>,
+    /// When the pending Taskwarrior tasks were last loaded
+    taskwarrior_loaded_at: Option<Instant>,
+    /// Whether `task export` is running in the background
+    taskwarrior_loading: bool,
+    /// Where background tracker calls send their results
+    tracker_tx: Sender<TrackerReply>,
+    /// Results of background tracker calls, picked up on each tick
+    tracker_rx: Receiver<TrackerReply>,
+    /// Past tasks, most recent first, for autocomplete and history recall
+    task_history: Vec<PastTask>,
+    /// Suggestion accepted with Tab, if still unedited
+    pub completion: Option<Completion>,
+    /// Position in the task history while recalling with Up/Down
+    history_recall: Option<usize>,
+    /// What was typed before recalling history, restored by Down
+    recall_draft: String,
+    /// Taskwarrior task the current pomodoro is for
+    linked_task: Option<TaskwarriorTask>,
+    /// todo.txt item the current pomodoro is for
+    pub linked_todo: Option<TodoItem>,
+    /// Whether the task description was suggested from the queue and not yet edited
+    pub task_suggested: bool,
+    /// One-line message shown on the timer view (e.g. integration errors)
+    pub notice: Option<String>,
+    /// Planned tasks shown in the plan view
+    pub plan: Plan,
+    /// Currently selected row in plan view
+    pub plan_selected: usize,
+    /// Title being typed for a new plan task, while adding one
+    pub plan_input: Option<LineEditor>,
+    /// Planned task the current pomodoro is for
+    linked_plan_task: Option<uuid::Uuid>,
+    /// Whether to ask for a retrospective when a work session completes
+    retro_prompt: bool,
+    /// Retrospective being filled in, shown over the current view
+    pub retro: Option<RetroForm>,
+    /// Most recently saved session, which a retrospective applies to
+    last_session_id: Option<uuid::Uuid>,
+    /// Key bindings, also used for the hint bars
+    pub keymap: Keymap,
+    /// Regions of the last frame that respond to the mouse, filled in by the widgets
+    pub mouse: RefCell<MouseTargets>,
+    /// Whether the key help overlay is showing
+    pub help: bool,
+    /// Command palette, while open
+    pub palette: Option<Palette>,
+    /// Timer profiles from the config file
+    profiles: BTreeMap<String, Profile>,
+    /// Name of the profile in use, if one was chosen
+    pub profile: Option<String>,
 }
 
 impl App {
@@ -125,6 +408,8 @@ impl App {
         let duration_secs = duration_minutes * 60;
         let storage = Storage::new()?;
         let sessions_cache = storage.load_sessions().unwrap_or_default();
+        let plan = storage.load_plan()?;
+        let (tracker_tx, tracker_rx) = mpsc::channel();
 
         Ok(Self {
             state: AppState::Idle,
@@ -132,261 +417,1045 @@ impl App {
             total_duration_secs: duration_secs,
             remaining_secs: duration_secs,
             task_description: String::new(),
+            task_input: LineEditor::default(),
             session_start: None,
             pause_start: None,
+            pauses: Vec::new(),
             storage,
             pomodoro_started_at: None,
             current_view: View::Timer,
             session_filter: SessionFilter::Week,
             sessions_cache,
             history_selected: 0,
+            history_detail: false,
+            history_edit: None,
+            history_confirm_delete: false,
+            history_search: LineEditor::default(),
+            history_searching: false,
+            history_sort: HistorySort::Date,
+            history_sort_ascending: false,
+            history_grouped: false,
+            history_collapsed: HashSet::new(),
+            history_jump: None,
+            stats_panel: StatsPanel::Overview,
+            heatmap_selected: Local::now().date_naive(),
+            stats_chart_offset: 0,
+            goal: DailyGoal::default(),
             timer_mode: TimerMode::Work,
             work_duration_secs: duration_secs,
             short_break_secs: short_break_minutes * 60,
             long_break_secs: long_break_minutes * 60,
             sessions_until_long_break: long_break_interval,
             work_sessions_completed: 0,
+            integrations: Integrations::default(),
+            taskwarrior_tasks: Vec::new(),
+            taskwarrior_selected: None,
+            taskwarrior_loaded_at: None,
+            taskwarrior_loading: false,
+            tracker_tx,
+            tracker_rx,
+            task_history: Vec::new(),
+            completion: None,
+            history_recall: None,
+            recall_draft: String::new(),
+            linked_task: None,
+            linked_todo: None,
+            task_suggested: false,
+            notice: None,
+            plan,
+            plan_selected: 0,
+            plan_input: None,
+            linked_plan_task: None,
+            retro_prompt: true,
+            retro: None,
+            last_session_id: None,
+            keymap: Keymap::default(),
+            mouse: RefCell::default(),
+            help: false,
+            palette: None,
+            profiles: BTreeMap::new(),
+            profile: None,
         })
     }
 
+    /// Enable integrations with external trackers
+    pub fn with_integrations(mut self, integrations: Integrations) -> Self {
+        self.integrations = integrations;
+        self
+    }
+
+    /// Track progress against a daily focus goal
+    pub fn with_goal(mut self, goal: DailyGoal) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Ask (or not) for a retrospective after each work session
+    pub fn with_retro_prompt(mut self, enabled: bool) -> Self {
+        self.retro_prompt = enabled;
+        self
+    }
+
+    /// Offer timer profiles to switch between
+    pub fn with_profiles(mut self, profiles: BTreeMap<String, Profile>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// Use custom key bindings
+    pub fn with_keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
     /// Handle an action and update state
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
+        let action = match action {
+            Action::Key(key) => self.resolve_key(key),
+            Action::Mouse(event) => self.resolve_mouse(event),
+            action => action,
+        };
+        if action == Action::Quit {
+            return self.quit();
+        }
+        if action == Action::Tick {
+            self.receive_tracker_replies();
+        }
+        // Any key closes the help overlay while the timer keeps ticking
+        if self.help && !matches!(action, Action::Tick | Action::None) {
+            self.help = false;
+            return Ok(());
+        }
+        if self.palette.is_some() && !matches!(action, Action::Tick | Action::None) {
+            return self.handle_palette_action(&action);
+        }
+        if self.retro.is_some() {
+            return self.handle_retro_action(&action);
+        }
+        if self.current_view == View::Plan && self.handle_plan_action(&action)? {
+            return Ok(());
+        }
+        if self.current_view == View::History && self.handle_history_action(&action)? {
+            return Ok(());
+        }
+        if self.current_view == View::Stats && self.handle_stats_action(&action) {
+            return Ok(());
+        }
+
         match (&self.state, &action) {
             // Tab navigation (available in non-input states)
-            (AppState::Idle | AppState::Running | AppState::Paused | AppState::WorkFinished | AppState::BreakFinished, Action::NextTab) => {
+            (AppState::Idle | AppState::Running | AppState::Paused | AppState::WorkFinished | AppState::BreakFinished, Action::NextTab | Action::CursorRight) => {
                 self.next_view();
             }
-            (AppState::Idle | AppState::Running | AppState::Paused | AppState::WorkFinished | AppState::BreakFinished, Action::PrevTab) => {
+            (AppState::Idle | AppState::Running | AppState::Paused | AppState::WorkFinished | AppState::BreakFinished, Action::PrevTab | Action::CursorLeft) => {
                 self.prev_view();
             }
-
-            // Scroll in history view
-            (_, Action::ScrollUp) => {
-                if self.current_view == View::History && self.history_selected > 0 {
-                    self.history_selected -= 1;
-                }
+            (_, Action::CycleFilter) if matches!(self.current_view, View::History | View::Stats) => {
+                self.cycle_filter();
             }
-            (_, Action::ScrollDown) => {
-                if self.current_view == View::History {
-                    let max = self.filtered_sessions().len().saturating_sub(1);
-                    if self.history_selected < max {
-                        self.history_selected += 1;
-                    }
-                }
+            (AppState::Idle | AppState::Running | AppState::Paused | AppState::WorkFinished | AppState::BreakFinished, Action::ShowView(view)) => {
+                self.show_view(*view);
             }
+            (_, Action::ShowHelp) => self.help = true,
+            (_, Action::OpenPalette) => self.palette = Some(Palette::default()),
 
             // Idle state
-            (AppState::Idle, Action::Confirm) => {
-                if self.current_view == View::Timer {
-                    self.state = AppState::EnteringTask;
-                    self.task_description.clear();
-                }
+            (AppState::Idle, Action::Confirm) if self.current_view == View::Timer => {
+                self.begin_task_entry(None);
             }
-            (AppState::Idle, Action::Input(c)) => {
-                // Handle special keys
-                match c {
-                    'q' | 'Q' => {
-                        self.should_quit = true;
-                    }
-                    'f' | 'F' => {
-                        if matches!(self.current_view, View::History | View::Stats) {
-                            self.cycle_filter();
-                        } else if self.current_view == View::Timer {
-                            // Start entering task with this character
-                            self.state = AppState::EnteringTask;
-                            self.task_description.clear();
-                            self.task_description.push(*c);
-                        }
-                    }
-                    _ => {
-                        if self.current_view == View::Timer {
-                            self.state = AppState::EnteringTask;
-                            self.task_description.clear();
-                            self.task_description.push(*c);
-                        }
-                    }
-                }
+            (AppState::Idle, Action::Repeat) if self.current_view == View::Timer && self.last_task().is_some() => {
+                self.repeat_last_task();
+            }
+            (AppState::Idle, Action::Input(c)) if self.current_view == View::Timer => {
+                // Start entering task with this character
+                self.begin_task_entry(Some(*c));
             }
 
             // Entering task state
-            (AppState::EnteringTask, Action::Input(c)) => {
-                self.task_description.push(*c);
+            (AppState::EnteringTask, Action::Input(_) | Action::Paste(_)) if self.task_suggested => {
+                // Typing over a suggested task replaces it, Backspace edits it
+                self.task_input.clear();
+                self.task_suggested = false;
+                self.task_input.handle(&action);
+                self.end_completion();
+            }
+            (AppState::EnteringTask, _) if self.task_input.handle(&action) => {
+                self.task_suggested = false;
+                self.end_completion();
+            }
+            (AppState::EnteringTask, Action::NextTab) => {
+                self.complete_task(true);
+            }
+            (AppState::EnteringTask, Action::PrevTab) => {
+                self.complete_task(false);
+            }
+            // Down moves into the Taskwarrior picker below the input and Up back out of it;
+            // otherwise Up/Down recall past tasks
+            (AppState::EnteringTask, Action::ScrollUp) if self.taskwarrior_selected.is_some() => {
+                self.taskwarrior_selected = self.taskwarrior_selected.and_then(|i| i.checked_sub(1));
             }
-            (AppState::EnteringTask, Action::Backspace) => {
-                self.task_description.pop();
+            (AppState::EnteringTask, Action::ScrollDown)
+                if self.history_recall.is_none() && !self.matching_taskwarrior_tasks().is_empty() =>
+            {
+                let count = self.matching_taskwarrior_tasks().len();
+                self.taskwarrior_selected = Some(match self.taskwarrior_selected {
+                    Some(i) => (i + 1).min(count - 1),
+                    None => 0,
+                });
             }
-            (AppState::EnteringTask, Action::Confirm) => {
-                if !self.task_description.trim().is_empty() {
-                    self.start_work_timer();
+            (AppState::EnteringTask, Action::ScrollUp) => {
+                self.recall_task(true);
+            }
+            (AppState::EnteringTask, Action::ScrollDown) => {
+                self.recall_task(false);
+            }
+            (AppState::EnteringTask, Action::Confirm)
+                if !self.task_input.text().trim().is_empty() || self.selected_taskwarrior_task().is_some() =>
+            {
+                let picked = self.selected_taskwarrior_task().cloned();
+                let input = self.task_input.take();
+                let (task, estimate) = split_estimate(&input);
+                self.task_description = match &picked {
+                    Some(picked) => picked.description.clone(),
+                    None => task.to_string(),
+                };
+                self.start_work_timer();
+                if picked.is_some() {
+                    self.linked_task = picked;
+                }
+                if let Some(estimate) = estimate {
+                    self.estimate_current_task(estimate)?;
                 }
             }
             (AppState::EnteringTask, Action::Cancel) => {
-                self.task_description.clear();
+                self.task_input.clear();
                 self.state = AppState::Idle;
             }
 
             // Running state
-            (AppState::Running, Action::Input(c)) => {
-                match c {
-                    ' ' => {
-                        // Only allow pause during work sessions
-                        if self.timer_mode == TimerMode::Work {
-                            self.pause_start = Some(Instant::now());
-                            self.state = AppState::Paused;
-                        }
-                    }
-                    's' | 'S' => {
-                        // Skip - during break, skip remaining break time
-                        if self.timer_mode != TimerMode::Work {
-                            self.state = AppState::BreakFinished;
-                        }
-                    }
-                    'r' | 'R' => {
-                        // Only save if it's a work session
-                        if self.timer_mode == TimerMode::Work {
-                            self.save_current_session(false)?;
-                        }
-                        self.reset();
-                    }
-                    'q' | 'Q' => {
-                        if self.timer_mode == TimerMode::Work {
-                            self.save_current_session(false)?;
-                        }
-                        self.should_quit = true;
-                    }
-                    'f' | 'F' => {
-                        if matches!(self.current_view, View::History | View::Stats) {
-                            self.cycle_filter();
-                        }
-                    }
-                    _ => {}
+            // Only allow pause during work sessions
+            (AppState::Running, Action::TogglePause) if self.timer_mode == TimerMode::Work => {
+                self.pause_start = Some(Instant::now());
+                self.state = AppState::Paused;
+            }
+            // Skip - during break, skip remaining break time
+            (AppState::Running, Action::Skip) if self.timer_mode != TimerMode::Work => {
+                self.state = AppState::BreakFinished;
+            }
+            (AppState::Running, Action::Stop) => {
+                // Only save if it's a work session
+                if self.timer_mode == TimerMode::Work {
+                    self.save_current_session(false)?;
                 }
+                self.reset();
             }
             (AppState::Running, Action::Tick) => {
                 self.update_timer();
             }
 
             // Paused state (only for work sessions)
-            (AppState::Paused, Action::Input(c)) => {
-                match c {
-                    ' ' => {
-                        // Resume - adjust session_start to account for pause duration
-                        if let (Some(pause_start), Some(session_start)) =
-                            (self.pause_start, self.session_start)
-                        {
-                            let pause_duration = pause_start.elapsed();
-                            self.session_start = Some(session_start + pause_duration);
-                        }
-                        self.pause_start = None;
-                        self.state = AppState::Running;
-                    }
-                    'r' | 'R' => {
-                        self.save_current_session(false)?;
-                        self.reset();
-                    }
-                    'q' | 'Q' => {
-                        self.save_current_session(false)?;
-                        self.should_quit = true;
-                    }
-                    'f' | 'F' => {
-                        if matches!(self.current_view, View::History | View::Stats) {
-                            self.cycle_filter();
-                        }
-                    }
-                    _ => {}
+            (AppState::Paused, Action::TogglePause) => {
+                // Resume - adjust session_start to account for pause duration
+                if let (Some(pause_start), Some(session_start)) = (self.pause_start, self.session_start) {
+                    let pause_duration = pause_start.elapsed();
+                    self.session_start = Some(session_start + pause_duration);
+                    self.pauses.push(Pause {
+                        started_at: Utc::now() - pause_duration,
+                        duration_secs: pause_duration.as_secs() as u32,
+                    });
                 }
+                self.pause_start = None;
+                self.state = AppState::Running;
+            }
+            (AppState::Paused, Action::Stop) => {
+                self.save_current_session(false)?;
+                self.reset();
             }
 
             // Work Finished state - offer break option
-            (AppState::WorkFinished, Action::Input(c)) => {
-                match c {
-                    'b' | 'B' => {
-                        // Start break (short or long based on completed sessions)
-                        self.start_break();
-                    }
-                    's' | 'S' => {
-                        // Skip break, go to idle
-                        self.reset();
-                    }
-                    'q' | 'Q' => {
-                        self.should_quit = true;
-                    }
-                    'f' | 'F' => {
-                        if matches!(self.current_view, View::History | View::Stats) {
-                            self.cycle_filter();
-                        }
-                    }
-                    _ => {}
-                }
+            (AppState::WorkFinished, Action::StartBreak) => {
+                // Start break (short or long based on completed sessions)
+                self.start_break();
             }
-            (AppState::WorkFinished, Action::Confirm) => {
-                // Skip break, start new task entry
+
+            // Both finished states
+            (AppState::WorkFinished | AppState::BreakFinished, Action::Confirm) => {
+                // Skip any break, start new task entry
                 self.timer_mode = TimerMode::Work;
                 self.total_duration_secs = self.work_duration_secs;
                 self.remaining_secs = self.work_duration_secs;
-                self.state = AppState::EnteringTask;
-                self.task_description.clear();
+                self.begin_task_entry(None);
+            }
+            (AppState::WorkFinished | AppState::BreakFinished, Action::Repeat) => {
+                // Go again on the same task
+                self.repeat_last_task();
+            }
+            (AppState::WorkFinished | AppState::BreakFinished, Action::Skip) => {
+                // Skip break, go to idle
+                self.reset();
+            }
+            (AppState::WorkFinished | AppState::BreakFinished, Action::Done) if self.can_mark_done() => {
+                self.complete_linked_todo();
+                self.complete_linked_plan_task()?;
+            }
+            (AppState::WorkFinished | AppState::BreakFinished, Action::Notes) => {
+                self.open_retro();
             }
 
-            // Break Finished state
-            (AppState::BreakFinished, Action::Confirm) => {
-                // Start new task entry
-                self.timer_mode = TimerMode::Work;
-                self.total_duration_secs = self.work_duration_secs;
-                self.remaining_secs = self.work_duration_secs;
-                self.state = AppState::EnteringTask;
-                self.task_description.clear();
-            }
-            (AppState::BreakFinished, Action::Input(c)) => {
-                match c {
-                    's' | 'S' => {
-                        // Go to idle
-                        self.reset();
-                    }
-                    'q' | 'Q' => {
-                        self.should_quit = true;
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Action a key press stands for: text editing while typing, otherwise its binding
+    fn resolve_key(&self, key: Key) -> Action {
+        // Ctrl-C quits the same way everywhere, whatever keys.toml says
+        if key.is_ctrl_c() {
+            return Action::Quit;
+        }
+        if self.is_typing() {
+            return key.text_action();
+        }
+
+        let action = self.keymap.action(self.key_context(), key);
+
+        // On the idle timer, characters start a task unless bound to something it can do
+        if let Some(c) = key.char()
+            && self.state == AppState::Idle
+            && self.current_view == View::Timer
+        {
+            let usable = match &action {
+                Some(Action::Repeat) => self.last_task().is_some(),
+                Some(action) => matches!(
+                    action,
+                    Action::Quit
+                        | Action::NextTab
+                        | Action::PrevTab
+                        | Action::CursorLeft
+                        | Action::CursorRight
+                        | Action::Confirm
+                        | Action::ShowHelp
+                        | Action::OpenPalette
+                ),
+                None => false,
+            };
+            if !usable {
+                return Action::Input(c);
+            }
+        }
+        action.unwrap_or(Action::None)
+    }
+
+    /// Turn a click or scroll into the action of the region it landed on
+    fn resolve_mouse(&self, event: MouseEvent) -> Action {
+        // Overlays cover the regions below them, so a click just closes them
+        if (self.help || self.palette.is_some()) && matches!(event.kind, MouseEventKind::Down(_)) {
+            return Action::Cancel;
+        }
+        self.mouse.borrow().action(event)
+    }
+
+    /// Bindings of the current view
+    pub fn key_context(&self) -> Context {
+        match self.current_view {
+            View::Timer => Context::Timer,
+            View::Plan => Context::Plan,
+            View::History => Context::History,
+            View::Stats => Context::Stats,
+        }
+    }
+
+    /// Timer commands that do something in the current state
+    pub fn timer_actions(&self) -> Vec<Action> {
+        let mut actions = match (&self.state, self.timer_mode) {
+            (AppState::Idle, _) if self.last_task().is_some() => vec![Action::Repeat],
+            (AppState::Running, TimerMode::Work) | (AppState::Paused, _) => vec![Action::TogglePause, Action::Stop],
+            (AppState::Running, _) => vec![Action::Skip, Action::Stop],
+            (AppState::WorkFinished, _) => vec![Action::StartBreak, Action::Repeat, Action::Skip, Action::Notes],
+            (AppState::BreakFinished, _) => vec![Action::Repeat, Action::Skip, Action::Notes],
+            _ => Vec::new(),
+        };
+        if matches!(self.state, AppState::WorkFinished | AppState::BreakFinished) && self.can_mark_done() {
+            actions.push(Action::Done);
+        }
+        actions
+    }
+
+    /// Whether keys are going to a text field or prompt rather than to bindings
+    fn is_typing(&self) -> bool {
+        self.state == AppState::EnteringTask
+            || self.palette.is_some()
+            || self.retro.is_some()
+            || self.history_searching
+            || self.history_jump.is_some()
+            || self.history_edit.is_some()
+            || self.history_confirm_delete
+            || self.plan_input.is_some()
+    }
+
+    /// Handle input while the command palette is open
+    fn handle_palette_action(&mut self, action: &Action) -> Result<()> {
+        let context = self.key_context();
+        let Some(palette) = &mut self.palette else {
+            return Ok(());
+        };
+        let matches = palette::matches(palette.editor.text(), context);
+
+        match action {
+            _ if palette.editor.handle(action) => {
+                palette.selected = 0;
+                palette.error = None;
+            }
+            Action::ScrollUp => palette.selected = palette.selected.saturating_sub(1),
+            Action::ScrollDown => palette.selected = (palette.selected + 1).min(matches.len().saturating_sub(1)),
+            Action::NextTab => {
+                // Complete the command name, keeping any arguments typed
+                if let Some(command) = matches.get(palette.selected) {
+                    let (_, args) = palette::split(palette.editor.text());
+                    let text = format!("{} {}", command.name(), args);
+                    palette.editor.set(text);
+                    palette.selected = 0;
+                }
+            }
+            Action::Cancel => self.palette = None,
+            Action::Confirm => {
+                let (query, args) = palette::split(palette.editor.text());
+                let Some(command) = matches.get(palette.selected).copied() else {
+                    palette.error = Some(format!("No command matches '{}'", query));
+                    return Ok(());
+                };
+                let args = args.to_string();
+                // Close first so the command sees the app as it will be afterwards
+                let mut palette = self.palette.take().unwrap_or_default();
+                if let Err(err) = self.run_palette_command(command, &args) {
+                    palette.error = Some(err.to_string());
+                    self.palette = Some(palette);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Run a command from the palette with its arguments
+    fn run_palette_command(&mut self, command: PaletteCommand, args: &str) -> Result<()> {
+        match command {
+            PaletteCommand::Bound(command) => {
+                if !args.is_empty() {
+                    bail!("{} takes no arguments", command.name);
+                }
+                // A view's commands run in that view
+                let view = match command.context {
+                    Context::Plan => Some(View::Plan),
+                    Context::History => Some(View::History),
+                    Context::Stats => Some(View::Stats),
+                    Context::Global | Context::Timer => None,
+                };
+                if let Some(view) = view
+                    && view != self.current_view
+                {
+                    self.show_view(view);
+                }
+                self.handle_action(command.action.clone())?;
+            }
+            PaletteCommand::Filter => {
+                let filter = SessionFilter::from_name(args)
+                    .ok_or_else(|| eyre!("Usage: filter today|week|month|year|all"))?;
+                self.set_filter(filter);
+            }
+            PaletteCommand::Profile => {
+                if args.is_empty() {
+                    bail!("Usage: profile NAME");
+                }
+                self.apply_profile(args)?;
+            }
+            PaletteCommand::Export => self.export_filtered(args)?,
+            PaletteCommand::Extend => {
+                // A bare number is minutes; otherwise a duration such as 90s or 1h
+                let secs = match args.parse::<u32>() {
+                    Ok(minutes) => minutes.checked_mul(60).ok_or_else(|| eyre!("Too long to extend by"))?,
+                    Err(_) => parse_duration(args).map_err(|_| eyre!("Usage: extend MINUTES, e.g. extend 5"))?,
+                };
+                self.extend_timer(secs)?;
+            }
+            PaletteCommand::View => {
+                let view = match args.to_ascii_lowercase().as_str() {
+                    "timer" => View::Timer,
+                    "plan" => View::Plan,
+                    "history" => View::History,
+                    "stats" => View::Stats,
+                    _ => bail!("Usage: view timer|plan|history|stats"),
+                };
+                self.show_view(view);
+            }
+        }
+        Ok(())
+    }
+
+    /// Use a profile's timer lengths from now on; a running timer keeps its length
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let Some(profile) = self.profiles.get(name) else {
+            if self.profiles.is_empty() {
+                bail!("No profiles configured - add [profiles.{}] to config.toml", name);
+            }
+            let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            bail!("No profile named '{}' (available: {})", name, names.join(", "));
+        };
+
+        // Check every length before changing any, so a bad profile leaves the timer as it was
+        let length = |field: &str, minutes: Option<u32>| -> Result<Option<u32>> {
+            match minutes {
+                None => Ok(None),
+                Some(0) => bail!("Profile '{}' has {} = 0; timer lengths must be at least a minute", name, field),
+                Some(minutes) => minutes
+                    .checked_mul(60)
+                    .map(Some)
+                    .ok_or_else(|| eyre!("Profile '{}' has {} = {}, which is too long", name, field, minutes)),
+            }
+        };
+        let duration = length("duration", profile.duration)?;
+        let short_break = length("short_break", profile.short_break)?;
+        let long_break = length("long_break", profile.long_break)?;
+
+        if let Some(secs) = duration {
+            self.work_duration_secs = secs;
+        }
+        if let Some(secs) = short_break {
+            self.short_break_secs = secs;
+        }
+        if let Some(secs) = long_break {
+            self.long_break_secs = secs;
+        }
+        if let Some(interval) = profile.long_break_interval {
+            self.sessions_until_long_break = interval;
+        }
+        if self.state == AppState::Idle {
+            self.total_duration_secs = self.work_duration_secs;
+            self.remaining_secs = self.work_duration_secs;
+        }
+        self.profile = Some(name.to_string());
+        self.notice = Some(format!(
+            "Profile {}: {} min work, {}/{} min breaks",
+            name,
+            self.work_duration_secs / 60,
+            self.short_break_secs / 60,
+            self.long_break_secs / 60
+        ));
+        Ok(())
+    }
+
+    /// Add time to the running or paused timer
+    fn extend_timer(&mut self, secs: u32) -> Result<()> {
+        if secs == 0 {
+            bail!("Usage: extend MINUTES, e.g. extend 5");
+        }
+        if !matches!(self.state, AppState::Running | AppState::Paused) {
+            bail!("Nothing to extend - no timer is running");
+        }
+        let (Some(total), Some(remaining)) = (
+            self.total_duration_secs.checked_add(secs),
+            self.remaining_secs.checked_add(secs),
+        ) else {
+            bail!("Too long to extend by");
+        };
+        self.total_duration_secs = total;
+        self.remaining_secs = remaining;
+        Ok(())
+    }
+
+    /// Export the sessions in the current filter, by default to a dated file in the working directory
+    fn export_filtered(&mut self, args: &str) -> Result<()> {
+        let (format, path) = palette::split(args);
+        let format = match format {
+            "" => ExportFormat::Csv,
+            name => ExportFormat::from_str(name, true).map_err(|_| eyre!("Usage: export csv|json|jsonl|ics [PATH]"))?,
+        };
+        let path = match path {
+            "" => PathBuf::from(format!(
+                "tomatocrab-{}.{}",
+                Local::now().format("%Y-%m-%d"),
+                format.extension()
+            )),
+            path => PathBuf::from(path),
+        };
+        let options = ExportOptions {
+            format,
+            columns: DEFAULT_COLUMNS.to_vec(),
+            timestamps: Timestamps::Local,
+        };
+
+        let sessions = self.filtered_sessions();
+        export_to_file(&sessions, &options, &path)?;
+        self.notice = Some(format!(
+            "Exported {} sessions ({}) to {}",
+            sessions.len(),
+            self.session_filter.label(),
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// Quit, saving an unfinished work session as interrupted
+    fn quit(&mut self) -> Result<()> {
+        if matches!(self.state, AppState::Running | AppState::Paused) && self.timer_mode == TimerMode::Work {
+            self.save_current_session(false)?;
+        }
+        self.should_quit = true;
+        Ok(())
+    }
+
+    /// Handle input while the retrospective form is open
+    fn handle_retro_action(&mut self, action: &Action) -> Result<()> {
+        let Some(form) = &mut self.retro else {
+            return Ok(());
+        };
+
+        match (form.field, action) {
+            (_, Action::Confirm) => self.save_retro()?,
+            (_, Action::Cancel) => self.retro = None,
+            (_, Action::NextTab | Action::ScrollDown) => form.field = form.field.next(),
+            (_, Action::PrevTab | Action::ScrollUp) => form.field = form.field.prev(),
+            (RetroField::Focus, Action::Input(c @ '1'..='5')) => {
+                form.focus = c.to_digit(10).map(|digit| digit as u8);
+            }
+            (RetroField::Focus, Action::CursorLeft) => {
+                form.focus = Some(form.focus.unwrap_or(1).saturating_sub(1).max(1));
+            }
+            (RetroField::Focus, Action::CursorRight) => {
+                form.focus = Some((form.focus.unwrap_or(0) + 1).min(5));
+            }
+            (RetroField::Focus, Action::Backspace | Action::Delete) => form.focus = None,
+            (RetroField::Focus, _) => {}
+            (RetroField::Accomplished, _) => {
+                form.accomplished.handle(action);
+            }
+            (RetroField::Notes, _) => {
+                form.notes.handle(action);
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the retrospective for the last saved session, prefilled with earlier answers
+    fn open_retro(&mut self) {
+        let Some(session_id) = self.last_session_id else {
+            return;
+        };
+        let mut form = RetroForm {
+            session_id,
+            ..RetroForm::default()
+        };
+        if let Some(session) = self.sessions_cache.iter().find(|session| session.id == session_id) {
+            form.accomplished.set(session.accomplished.clone().unwrap_or_default());
+            form.focus = session.focus;
+            form.notes.set(session.notes.clone().unwrap_or_default());
+        }
+        self.retro = Some(form);
+    }
+
+    /// Store the retrospective answers on their session
+    fn save_retro(&mut self) -> Result<()> {
+        let Some(form) = self.retro.take() else {
+            return Ok(());
+        };
+        let accomplished = Some(form.accomplished.text().trim().to_string()).filter(|text| !text.is_empty());
+        let notes = Some(form.notes.text().trim().to_string()).filter(|text| !text.is_empty());
+
+        self.storage.update_session(form.session_id, |session| {
+            session.accomplished = accomplished;
+            session.focus = form.focus;
+            session.notes = notes;
+        })?;
+        self.refresh_sessions();
+        Ok(())
+    }
+
+    /// Handle actions for the history view's search, sorting and detail pane
+    ///
+    /// Returns `true` if the action was consumed.
+    fn handle_history_action(&mut self, action: &Action) -> Result<bool> {
+        if *action == Action::Tick {
+            return Ok(false);
+        }
+
+        if self.history_searching {
+            match action {
+                _ if self.history_search.handle(action) => self.history_selected = 0,
+                Action::Confirm => self.history_searching = false,
+                Action::Cancel => {
+                    self.history_searching = false;
+                    self.history_search.clear();
+                    self.history_selected = 0;
+                }
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if let Some(jump) = &mut self.history_jump {
+            match action {
+                _ if jump.editor.handle(action) => jump.invalid = false,
+                Action::Confirm => match parse_day(jump.editor.text(), Local::now().date_naive()) {
+                    Ok(date) => {
+                        self.history_jump = None;
+                        self.jump_to_date(date);
                     }
-                    'f' | 'F' => {
-                        if matches!(self.current_view, View::History | View::Stats) {
-                            self.cycle_filter();
-                        }
+                    Err(_) => jump.invalid = true,
+                },
+                Action::Cancel => self.history_jump = None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if let Some(edit) = &mut self.history_edit {
+            match action {
+                _ if edit.editor_mut().handle(action) => {}
+                Action::Confirm => self.save_history_edit()?,
+                Action::Cancel => self.history_edit = None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if self.history_confirm_delete {
+            self.history_confirm_delete = false;
+            if let (Action::Input('y' | 'Y'), Some(id)) = (action, self.selected_session().map(|s| s.id)) {
+                self.storage.delete_session(id)?;
+                self.refresh_sessions();
+                self.history_selected = self
+                    .history_selected
+                    .min(self.history_rows().len().saturating_sub(1));
+                self.history_detail = false;
+            }
+            return Ok(true);
+        }
+
+        if let (Action::Confirm, Some(HistoryRow::Day { date, .. })) =
+            (action, self.history_rows().get(self.history_selected))
+        {
+            let date = *date;
+            if !self.history_collapsed.remove(&date) {
+                self.history_collapsed.insert(date);
+            }
+            return Ok(true);
+        }
+
+        let last = self.history_rows().len().saturating_sub(1);
+        match action {
+            // A second click on a row opens it, like Enter
+            Action::SelectRow(row) if *row == self.history_selected => {
+                return self.handle_history_action(&Action::Confirm);
+            }
+            Action::SelectRow(row) => self.history_selected = (*row).min(last),
+            Action::ScrollUp => self.history_selected = self.history_selected.saturating_sub(1),
+            Action::ScrollDown => self.history_selected = (self.history_selected + 1).min(last),
+            Action::PageUp => self.history_selected = self.history_selected.saturating_sub(HISTORY_PAGE),
+            Action::PageDown => self.history_selected = (self.history_selected + HISTORY_PAGE).min(last),
+            Action::Home => self.history_selected = 0,
+            Action::End => self.history_selected = last,
+            Action::Search => self.history_searching = true,
+            Action::Sort => {
+                self.history_sort = self.history_sort.next();
+                self.history_selected = 0;
+            }
+            Action::ReverseSort => {
+                self.history_sort_ascending = !self.history_sort_ascending;
+                self.history_selected = 0;
+            }
+            Action::Group => {
+                self.history_grouped = !self.history_grouped;
+                self.history_selected = 0;
+                self.history_detail = false;
+            }
+            Action::JumpToDate => self.history_jump = Some(DateJump::default()),
+            Action::Cancel if !self.history_detail && !self.history_search.text().is_empty() => {
+                self.history_search.clear();
+                self.history_selected = 0;
+            }
+            _ => return self.handle_history_detail_action(action),
+        }
+        Ok(true)
+    }
+
+    /// Handle actions that open or act on the selected session's details
+    fn handle_history_detail_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(session) = self.selected_session() else {
+            return Ok(false);
+        };
+        match action {
+            Action::Confirm => self.history_detail = !self.history_detail,
+            Action::Cancel if self.history_detail => self.history_detail = false,
+            Action::EditTask if self.history_detail => {
+                self.history_edit = Some(SessionEdit::Task(LineEditor::new(session.task.clone())));
+            }
+            Action::EditNotes if self.history_detail => {
+                let notes = session.notes.clone().unwrap_or_default();
+                self.history_edit = Some(SessionEdit::Notes(LineEditor::new(notes)));
+            }
+            Action::DeleteSelected if self.history_detail => self.history_confirm_delete = true,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Session under the cursor in the history view
+    pub fn selected_session(&self) -> Option<&Session> {
+        match self.history_rows().get(self.history_selected) {
+            Some(HistoryRow::Session(session)) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// Rows of the history table, under day headings when grouped
+    pub fn history_rows(&self) -> Vec<HistoryRow<'_>> {
+        let sessions = self.history_sessions();
+        if !self.history_grouped {
+            return sessions.into_iter().map(HistoryRow::Session).collect();
+        }
+
+        let mut days: Vec<(NaiveDate, Vec<&Session>)> = Vec::new();
+        for session in sessions {
+            let date = session.started_at.with_timezone(&Local).date_naive();
+            match days.iter_mut().find(|(day, _)| *day == date) {
+                Some((_, day_sessions)) => day_sessions.push(session),
+                None => days.push((date, vec![session])),
+            }
+        }
+        days.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+        if self.history_sort_ascending {
+            days.reverse();
+        }
+
+        let mut rows = Vec::new();
+        for (date, sessions) in days {
+            let collapsed = self.history_collapsed.contains(&date);
+            rows.push(HistoryRow::Day { date, sessions: sessions.clone() });
+            if !collapsed {
+                rows.extend(sessions.into_iter().map(HistoryRow::Session));
+            }
+        }
+        rows
+    }
+
+    /// Move the history cursor to `date`, or the nearest day with sessions
+    ///
+    /// Widens the filter to all time if the current one hides that day.
+    fn jump_to_date(&mut self, date: NaiveDate) {
+        let today = Local::now().date_naive();
+        let visible = self.sessions_cache.iter().any(|session| {
+            session.started_at.with_timezone(&Local).date_naive() == date
+                && self.session_filter.matches(session, today)
+        });
+        if !visible {
+            self.session_filter = SessionFilter::All;
+        }
+        self.history_collapsed.remove(&date);
+        self.history_detail = false;
+
+        self.history_selected = self
+            .history_rows()
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, row)| (row.date() - date).num_days().abs())
+            .map_or(0, |(index, _)| index);
+    }
+
+    /// Sessions shown in the history view, after filtering, searching and sorting
+    pub fn history_sessions(&self) -> Vec<&Session> {
+        let query = self.history_search.text();
+        let mut rows: Vec<&Session> = self
+            .filtered_sessions()
+            .into_iter()
+            .filter(|session| session.matches_search(query))
+            .collect();
+
+        // Newest first within equal keys
+        rows.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+        match self.history_sort {
+            HistorySort::Date => {}
+            HistorySort::Duration => rows.sort_by_key(|session| std::cmp::Reverse(session.duration_secs)),
+            HistorySort::Status => rows.sort_by_key(|session| (!session.completed, session.manual)),
+            HistorySort::Task => rows.sort_by_cached_key(|session| session.task.to_lowercase()),
+        }
+        if self.history_sort_ascending {
+            rows.reverse();
+        }
+        rows
+    }
+
+    /// Save the field being edited in the history view
+    fn save_history_edit(&mut self) -> Result<()> {
+        let (Some(edit), Some(id)) = (self.history_edit.take(), self.selected_session().map(|s| s.id)) else {
+            return Ok(());
+        };
+        let value = edit.editor().text().trim().to_string();
+
+        match edit {
+            // A session always needs a task
+            SessionEdit::Task(_) if value.is_empty() => return Ok(()),
+            SessionEdit::Task(_) => self.storage.update_session(id, |session| session.task = value)?,
+            SessionEdit::Notes(_) => self
+                .storage
+                .update_session(id, |session| session.notes = Some(value).filter(|notes| !notes.is_empty()))?,
+        };
+        self.refresh_sessions();
+        Ok(())
+    }
+
+    /// Handle actions specific to the plan view
+    ///
+    /// Returns `true` if the action was consumed.
+    fn handle_plan_action(&mut self, action: &Action) -> Result<bool> {
+        // Typing a new task title captures all input
+        if let Some(input) = &mut self.plan_input {
+            match action {
+                _ if input.handle(action) => {}
+                Action::Confirm => {
+                    let (title, estimate) = split_estimate(input.text());
+                    let title = title.to_string();
+                    self.plan_input = None;
+                    if !title.is_empty() {
+                        self.plan.add(title, estimate);
+                        self.plan_selected = self.plan_rows().len().saturating_sub(1);
+                        self.storage.save_plan(&self.plan)?;
                     }
-                    _ => {}
                 }
+                Action::Cancel => self.plan_input = None,
+                Action::NextTab | Action::PrevTab => {}
+                _ => return Ok(false),
             }
+            return Ok(true);
+        }
 
-            _ => {}
+        let rows = self.plan_rows();
+        let selected = rows.get(self.plan_selected).copied();
+
+        match (action, selected) {
+            (Action::ScrollUp, _) => {
+                self.plan_selected = self.plan_selected.saturating_sub(1);
+            }
+            (Action::ScrollDown, _) => {
+                if self.plan_selected + 1 < rows.len() {
+                    self.plan_selected += 1;
+                }
+            }
+            (Action::AddTask, _) => {
+                self.plan_input = Some(LineEditor::default());
+            }
+            (Action::IncreaseEstimate, Some(i)) => {
+                let task = &mut self.plan.tasks[i];
+                task.estimate = Some(task.estimate.unwrap_or(0) + 1);
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::DecreaseEstimate, Some(i)) => {
+                let task = &mut self.plan.tasks[i];
+                task.estimate = task.estimate.and_then(|n| n.checked_sub(1)).filter(|n| *n > 0);
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::ToggleDone, Some(i)) => {
+                self.plan.tasks[i].done = !self.plan.tasks[i].done;
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::MoveUp, Some(i)) if self.plan_selected > 0 => {
+                self.plan.swap(i, rows[self.plan_selected - 1]);
+                self.plan_selected -= 1;
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::MoveDown, Some(i)) if self.plan_selected + 1 < rows.len() => {
+                self.plan.swap(i, rows[self.plan_selected + 1]);
+                self.plan_selected += 1;
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::CarryOver, Some(i)) if !self.plan.tasks[i].done => {
+                self.plan.carry_over(i);
+                self.clamp_plan_selection();
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::DeleteSelected, Some(i)) => {
+                self.plan.tasks.remove(i);
+                self.clamp_plan_selection();
+                self.storage.save_plan(&self.plan)?;
+            }
+            (Action::Confirm, Some(i))
+                if matches!(
+                    self.state,
+                    AppState::Idle | AppState::WorkFinished | AppState::BreakFinished
+                ) && !self.plan.tasks[i].done =>
+            {
+                let task = self.plan.tasks[i].clone();
+                self.start_plan_task(&task);
+            }
+            _ => return Ok(false),
         }
 
-        Ok(())
+        Ok(true)
+    }
+
+    /// Indices into the plan of the rows shown in the plan view
+    pub fn plan_rows(&self) -> Vec<usize> {
+        self.plan.indices_for(Local::now().date_naive())
+    }
+
+    /// Completed pomodoros recorded against a planned task
+    pub fn plan_task_pomodoros(&self, task: &PlanTask) -> usize {
+        self.sessions_cache
+            .iter()
+            .filter(|session| session.completed && session.task_id == Some(task.id))
+            .count()
+    }
+
+    /// Keep the plan selection within the visible rows
+    fn clamp_plan_selection(&mut self) {
+        let max = self.plan_rows().len().saturating_sub(1);
+        self.plan_selected = self.plan_selected.min(max);
+    }
+
+    /// Start a work timer for a planned task
+    fn start_plan_task(&mut self, task: &PlanTask) {
+        self.task_description = task.title.clone();
+        self.start_work_timer();
+        self.linked_plan_task = Some(task.id);
+        self.current_view = View::Timer;
     }
 
     /// Switch to next view
     pub fn next_view(&mut self) {
-        self.current_view = self.current_view.next();
-        if self.current_view != View::Timer {
-            self.refresh_sessions();
-        }
-        self.history_selected = 0;
+        self.show_view(self.current_view.next());
     }
 
     /// Switch to previous view
     pub fn prev_view(&mut self) {
-        self.current_view = self.current_view.prev();
+        self.show_view(self.current_view.prev());
+    }
+
+    /// Switch to a view
+    pub fn show_view(&mut self, view: View) {
+        self.current_view = view;
         if self.current_view != View::Timer {
             self.refresh_sessions();
         }
         self.history_selected = 0;
+        self.history_detail = false;
     }
 
     /// Cycle through session filters
     pub fn cycle_filter(&mut self) {
-        self.session_filter = match self.session_filter {
+        self.set_filter(match self.session_filter {
             SessionFilter::Today => SessionFilter::Week,
-            SessionFilter::Week => SessionFilter::All,
+            SessionFilter::Week => SessionFilter::Month,
+            SessionFilter::Month => SessionFilter::Year,
+            SessionFilter::Year => SessionFilter::All,
             SessionFilter::All => SessionFilter::Today,
-        };
+        });
+    }
+
+    /// Show sessions from a period in the history and stats views
+    pub fn set_filter(&mut self, filter: SessionFilter) {
+        self.session_filter = filter;
         self.history_selected = 0;
+        self.stats_chart_offset = 0;
     }
 
     /// Refresh sessions cache from storage
@@ -396,26 +1465,25 @@ impl App {
 
     /// Get filtered sessions based on current filter
     pub fn filtered_sessions(&self) -> Vec<&Session> {
-        let now = Local::now();
-        let today = now.date_naive();
+        let today = Local::now().date_naive();
 
         self.sessions_cache
             .iter()
-            .filter(|session| {
-                let session_date = session.started_at.with_timezone(&Local).date_naive();
-
-                match self.session_filter {
-                    SessionFilter::Today => session_date == today,
-                    SessionFilter::Week => {
-                        let week_ago = today - chrono::Duration::days(7);
-                        session_date >= week_ago
-                    }
-                    SessionFilter::All => true,
-                }
-            })
+            .filter(|session| self.session_filter.matches(session, today))
             .collect()
     }
 
+    /// Sessions in the period before the current filter's, or `None` for all time
+    pub fn previous_sessions(&self) -> Option<Vec<&Session>> {
+        let (from, to) = self.session_filter.previous_range(Local::now().date_naive())?;
+        Some(
+            self.sessions_cache
+                .iter()
+                .filter(|session| (from..=to).contains(&session.started_at.with_timezone(&Local).date_naive()))
+                .collect(),
+        )
+    }
+
     /// Get daily focus time in seconds for the past 7 days
     /// Returns a vector of 7 values (oldest to newest)
     pub fn daily_focus_data(&self) -> Vec<u64> {
@@ -442,37 +1510,262 @@ impl App {
             .sum()
     }
 
-    /// Get weekly focus data with day labels (for bar chart)
-    /// Returns (day_label, focus_seconds) for the past 7 days
-    pub fn weekly_bar_data(&self) -> Vec<(&'static str, u64)> {
-        let now = Local::now();
-        let today = now.date_naive();
+    /// Focus chart for the current filter's scale, `stats_chart_offset` periods back
+    pub fn focus_chart(&self) -> FocusChart {
+        FocusChart::new(
+            &self.sessions_cache,
+            ChartScale::for_filter(self.session_filter),
+            Local::now().date_naive(),
+            self.stats_chart_offset,
+        )
+    }
 
-        (0..7)
-            .rev()
-            .map(|days_ago| {
-                let target_date = today - chrono::Duration::days(days_ago);
-                let day_label = match target_date.weekday() {
-                    chrono::Weekday::Mon => "Mon",
-                    chrono::Weekday::Tue => "Tue",
-                    chrono::Weekday::Wed => "Wed",
-                    chrono::Weekday::Thu => "Thu",
-                    chrono::Weekday::Fri => "Fri",
-                    chrono::Weekday::Sat => "Sat",
-                    chrono::Weekday::Sun => "Sun",
-                };
-                (day_label, self.focus_time_for_date(target_date))
-            })
+    /// Average self-rated focus for each hour of the day with rated sessions
+    /// Returns (hour, average_focus) in hour order, for the current filter
+    pub fn focus_by_hour(&self) -> Vec<(u32, f64)> {
+        let mut totals = [(0u32, 0u32); 24];
+        for session in self.filtered_sessions() {
+            if let Some(focus) = session.focus {
+                let hour = session.started_at.with_timezone(&Local).hour() as usize;
+                totals[hour].0 += focus as u32;
+                totals[hour].1 += 1;
+            }
+        }
+
+        totals
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, count))| *count > 0)
+            .map(|(hour, (sum, count))| (hour as u32, *sum as f64 / *count as f64))
             .collect()
     }
 
+    /// Switch to task entry, optionally seeding the description with a typed character
+    fn begin_task_entry(&mut self, initial: Option<char>) {
+        self.state = AppState::EnteringTask;
+        self.task_description.clear();
+        self.task_input.set(initial.map(String::from).unwrap_or_default());
+        self.linked_task = None;
+        self.taskwarrior_selected = None;
+        self.linked_todo = None;
+        self.linked_plan_task = None;
+        self.task_suggested = false;
+        self.completion = None;
+        self.history_recall = None;
+        self.task_history = suggest::past_tasks(&self.sessions_cache, Utc::now());
+
+        // Suggest the next task from the queue
+        if let (None, Some(todo_txt)) = (initial, &self.integrations.todo_txt) {
+            match todo_txt.next_task() {
+                Ok(Some(item)) => {
+                    self.task_input.set(item.text);
+                    self.task_suggested = true;
+                }
+                Ok(None) => {}
+                Err(err) => self.notice = Some(format!("todo.txt: {}", err)),
+            }
+        }
+
+        // `task export` can be slow, so reuse a recent list rather than run it every time
+        let fresh = self
+            .taskwarrior_loaded_at
+            .is_some_and(|loaded_at| loaded_at.elapsed() < TASKWARRIOR_REFRESH);
+        if let Some(taskwarrior) = self.integrations.taskwarrior.clone()
+            && !fresh
+            && !self.taskwarrior_loading
+        {
+            self.taskwarrior_loading = true;
+            let tx = self.tracker_tx.clone();
+            thread::spawn(move || {
+                let _ = tx.send(TrackerReply::PendingTasks(taskwarrior.pending_tasks()));
+            });
+        }
+    }
+
+    /// Apply the results of tracker calls that finished in the background
+    fn receive_tracker_replies(&mut self) {
+        while let Ok(reply) = self.tracker_rx.try_recv() {
+            match reply {
+                TrackerReply::PendingTasks(Ok(tasks)) => {
+                    self.taskwarrior_loading = false;
+                    self.taskwarrior_tasks = tasks;
+                    self.taskwarrior_selected = None;
+                    self.taskwarrior_loaded_at = Some(Instant::now());
+                }
+                TrackerReply::PendingTasks(Err(err)) => {
+                    self.taskwarrior_loading = false;
+                    self.taskwarrior_tasks.clear();
+                    self.taskwarrior_selected = None;
+                    self.notice = Some(format!("Taskwarrior: {}", err));
+                }
+                TrackerReply::Synced(errors) => self.notice = Some(errors.join(" | ")),
+            }
+        }
+    }
+
+    /// Pending Taskwarrior tasks whose description contains what has been typed
+    pub fn matching_taskwarrior_tasks(&self) -> Vec<&TaskwarriorTask> {
+        let query = if self.task_suggested {
+            String::new()
+        } else {
+            self.task_input.text().trim().to_lowercase()
+        };
+        self.taskwarrior_tasks
+            .iter()
+            .filter(|task| query.is_empty() || task.description.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// The Taskwarrior task highlighted in the picker, if any
+    fn selected_taskwarrior_task(&self) -> Option<&TaskwarriorTask> {
+        self.taskwarrior_selected
+            .and_then(|i| self.matching_taskwarrior_tasks().get(i).copied())
+    }
+
+    /// Past tasks matching what has been typed, best first
+    pub fn task_suggestions(&self) -> Vec<Suggestion> {
+        let query = match &self.completion {
+            Some(completion) => completion.query.as_str(),
+            None if self.task_suggested => "",
+            None => self.task_input.text(),
+        };
+
+        let mut ranked: Vec<(f64, Suggestion)> = Vec::new();
+        for task in &self.task_history {
+            if let Some(score) = suggest::fuzzy_score(query, &task.text) {
+                ranked.push((
+                    suggest::rank_score(score, task.frecency),
+                    Suggestion {
+                        text: task.text.clone(),
+                    },
+                ));
+            }
+        }
+
+        // Offering exactly what was typed adds nothing
+        ranked.retain(|(_, suggestion)| suggestion.text != query.trim());
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+        ranked
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, suggestion)| suggestion)
+            .collect()
+    }
+
+    /// Accept the next (or previous) suggestion, cycling on repeated presses
+    fn complete_task(&mut self, forward: bool) {
+        let completion = match self.completion.take() {
+            Some(completion) => completion,
+            None => Completion {
+                query: if self.task_suggested {
+                    String::new()
+                } else {
+                    self.task_input.text().to_string()
+                },
+                // Tab accepts the first suggestion, Shift-Tab the last
+                selected: usize::MAX,
+            },
+        };
+        self.completion = Some(completion);
+
+        let count = self.task_suggestions().len();
+        let Some(completion) = &mut self.completion else {
+            return;
+        };
+        if count == 0 {
+            self.completion = None;
+            return;
+        }
+
+        completion.selected = match (forward, completion.selected) {
+            (true, usize::MAX) => 0,
+            (false, usize::MAX) => count - 1,
+            (true, i) => (i + 1) % count,
+            (false, i) => (i + count - 1) % count,
+        };
+        let selected = completion.selected;
+        let text = self.task_suggestions()[selected].text.clone();
+        self.task_input.set(text);
+        self.task_suggested = false;
+    }
+
+    /// Step back (or forward) through previously used tasks
+    fn recall_task(&mut self, older: bool) {
+        self.completion = None;
+        let next = match (self.history_recall, older) {
+            (None, true) if !self.task_history.is_empty() => Some(0),
+            (None, _) => return,
+            (Some(i), true) => Some((i + 1).min(self.task_history.len() - 1)),
+            (Some(0), false) => None,
+            (Some(i), false) => Some(i - 1),
+        };
+
+        if self.history_recall.is_none() {
+            self.recall_draft = self.task_input.take();
+        }
+        self.history_recall = next;
+        let text = match next {
+            Some(i) => self.task_history[i].text.clone(),
+            None => std::mem::take(&mut self.recall_draft),
+        };
+        self.task_input.set(text);
+        self.task_suggested = false;
+    }
+
+    /// Forget suggestion and history positions after the text is edited
+    fn end_completion(&mut self) {
+        self.completion = None;
+        self.history_recall = None;
+        self.taskwarrior_selected = None;
+    }
+
+    /// Task of the most recently started session
+    pub fn last_task(&self) -> Option<&str> {
+        self.sessions_cache
+            .iter()
+            .max_by_key(|session| session.started_at)
+            .map(|session| session.task.trim())
+            .filter(|task| !task.is_empty())
+    }
+
+    /// Start a work timer on the same task as the last session
+    fn repeat_last_task(&mut self) {
+        let Some(task) = self.last_task().map(str::to_string) else {
+            return;
+        };
+        self.task_description = task;
+        self.start_work_timer();
+    }
+
     /// Start a work timer
     fn start_work_timer(&mut self) {
+        self.notice = None;
+        self.task_suggested = false;
+        if let Some(todo_txt) = &self.integrations.todo_txt {
+            match todo_txt.find_open(&self.task_description) {
+                Ok(item) => self.linked_todo = item,
+                Err(err) => self.notice = Some(format!("todo.txt: {}", err)),
+            }
+        }
+        let task = self.task_description.trim();
+        self.linked_task = self
+            .taskwarrior_tasks
+            .iter()
+            .find(|pending| pending.description == task)
+            .cloned();
+        // Typing the title of a planned task counts toward it too
+        self.linked_plan_task = self
+            .plan_rows()
+            .into_iter()
+            .map(|i| &self.plan.tasks[i])
+            .find(|planned| !planned.done && planned.title == task)
+            .map(|planned| planned.id);
         self.timer_mode = TimerMode::Work;
         self.total_duration_secs = self.work_duration_secs;
         self.remaining_secs = self.work_duration_secs;
         self.session_start = Some(Instant::now());
         self.pomodoro_started_at = Some(Utc::now());
+        self.pauses.clear();
         self.state = AppState::Running;
     }
 
@@ -503,8 +1796,14 @@ impl App {
                     // Work session completed - save and offer break
                     self.state = AppState::WorkFinished;
                     self.work_sessions_completed += 1;
-                    let _ = self.save_current_session(true);
+                    let saved = self.save_current_session(true);
+                    self.sync_integrations();
                     self.refresh_sessions();
+                    match saved {
+                        Ok(()) if self.retro_prompt => self.open_retro(),
+                        Ok(()) => {}
+                        Err(err) => self.notice = Some(format!("Session not saved: {}", err)),
+                    }
                 } else {
                     // Break completed - NOT saved to history
                     self.state = AppState::BreakFinished;
@@ -515,8 +1814,121 @@ impl App {
         }
     }
 
+    /// Report a completed work session to the enabled integrations
+    fn sync_integrations(&mut self) {
+        let Some(started_at) = self.pomodoro_started_at else {
+            return;
+        };
+        let ended_at = Utc::now();
+        let minutes = self.elapsed_secs() / 60;
+
+        if let (Some(todo_txt), Some(item)) = (&self.integrations.todo_txt, &self.linked_todo) {
+            match todo_txt.record_pomodoro(&item.text) {
+                Ok(updated) => self.linked_todo = updated,
+                Err(err) => self.notice = Some(format!("todo.txt: {}", err)),
+            }
+        }
+
+        // Taskwarrior and Timewarrior run in the background and report failures on a later tick
+        let annotate = self.integrations.taskwarrior.clone().zip(self.linked_task.clone());
+        let track = self.integrations.timewarrior.clone().map(|timewarrior| {
+            let mut tags = vec![self.task_description.trim().to_string()];
+            if let Some(project) = self.linked_task.as_ref().and_then(|task| task.project.clone()) {
+                tags.push(project);
+            }
+            (timewarrior, tags)
+        });
+        if annotate.is_none() && track.is_none() {
+            return;
+        }
+
+        let tx = self.tracker_tx.clone();
+        thread::spawn(move || {
+            let mut errors = Vec::new();
+            if let Some((taskwarrior, task)) = annotate
+                && let Err(err) = taskwarrior.annotate(&task.uuid, &format!("Pomodoro completed ({} min)", minutes))
+            {
+                errors.push(format!("Taskwarrior: {}", err));
+            }
+            if let Some((timewarrior, tags)) = track
+                && let Err(err) = timewarrior.track(started_at, ended_at, &tags)
+            {
+                errors.push(format!("Timewarrior: {}", err));
+            }
+            if !errors.is_empty() {
+                let _ = tx.send(TrackerReply::Synced(errors));
+            }
+        });
+    }
+
+    /// Whether the task the last pomodoro was for can be marked done
+    pub fn can_mark_done(&self) -> bool {
+        self.linked_todo.is_some() || self.linked_plan_task.is_some()
+    }
+
+    /// Record an estimate for the task being worked on
+    ///
+    /// A task that isn't planned yet is added to today's plan, so that its sessions share
+    /// an id and its estimate can be compared with the pomodoros it takes.
+    fn estimate_current_task(&mut self, estimate: u32) -> Result<()> {
+        let linked = self
+            .linked_plan_task
+            .and_then(|id| self.plan.tasks.iter_mut().find(|task| task.id == id));
+        match linked {
+            Some(task) => task.estimate = Some(estimate),
+            None => {
+                let title = self.task_description.trim().to_string();
+                self.linked_plan_task = Some(self.plan.add(title, Some(estimate)));
+            }
+        }
+        self.storage.save_plan(&self.plan)
+    }
+
+    /// Mark the planned task the last pomodoro was for as done
+    fn complete_linked_plan_task(&mut self) -> Result<()> {
+        let Some(id) = self.linked_plan_task.take() else {
+            return Ok(());
+        };
+        let Some(task) = self.plan.tasks.iter_mut().find(|task| task.id == id) else {
+            return Ok(());
+        };
+        task.done = true;
+        let title = task.title.clone();
+        let estimate = task.estimate;
+        self.storage.save_plan(&self.plan)?;
+        self.refresh_sessions();
+
+        let pomodoros = self.sessions_cache.iter().filter(|session| session.completed && session.task_id == Some(id)).count();
+        self.notice = Some(match estimate {
+            Some(estimate) => format!("Marked \"{}\" done ({} of {} estimated pomodoros)", title, pomodoros, estimate),
+            None => format!("Marked \"{}\" done ({} pomodoros)", title, pomodoros),
+        });
+        Ok(())
+    }
+
+    /// Mark the todo.txt task the last pomodoro was for as done
+    fn complete_linked_todo(&mut self) {
+        let (Some(todo_txt), Some(item)) = (&self.integrations.todo_txt, &self.linked_todo) else {
+            return;
+        };
+        match todo_txt.complete(&item.text) {
+            Ok(_) => {
+                self.notice = Some(format!(
+                    "Marked \"{}\" done ({} pomodoros)",
+                    item.text,
+                    item.progress()
+                ));
+                self.linked_todo = None;
+            }
+            Err(err) => self.notice = Some(format!("todo.txt: {}", err)),
+        }
+    }
+
     /// Reset the app to idle state
     fn reset(&mut self) {
+        self.linked_task = None;
+        self.linked_todo = None;
+        self.linked_plan_task = None;
         self.state = AppState::Idle;
         self.timer_mode = TimerMode::Work;
         self.total_duration_secs = self.work_duration_secs;
@@ -524,23 +1936,39 @@ impl App {
         self.task_description.clear();
         self.session_start = None;
         self.pause_start = None;
+        self.pauses.clear();
         self.pomodoro_started_at = None;
         self.refresh_sessions();
     }
 
     /// Save the current session
     fn save_current_session(&mut self, completed: bool) -> Result<()> {
-        if let Some(started_at) = self.pomodoro_started_at {
-            if !self.task_description.trim().is_empty() {
-                let duration_secs = self.total_duration_secs - self.remaining_secs;
-                let session = Session::new(
-                    self.task_description.clone(),
-                    started_at,
-                    duration_secs,
-                    completed,
-                );
-                self.storage.save_session(session)?;
+        if let Some(started_at) = self.pomodoro_started_at
+            && !self.task_description.trim().is_empty()
+        {
+            let duration_secs = self.total_duration_secs - self.remaining_secs;
+            let mut session = Session::new(
+                self.task_description.clone(),
+                started_at,
+                duration_secs,
+                completed,
+            );
+            session.task_id = self.linked_plan_task;
+            session.planned_secs = Some(self.total_duration_secs);
+            session.pauses = self.pauses.clone();
+            // Stopping while paused ends the pause too
+            if let Some(pause_start) = self.pause_start {
+                let pause_duration = pause_start.elapsed();
+                session.pauses.push(Pause {
+                    started_at: Utc::now() - pause_duration,
+                    duration_secs: pause_duration.as_secs() as u32,
+                });
             }
+            // Notes only ever go to a session that made it to disk
+            self.last_session_id = None;
+            let id = session.id;
+            self.storage.save_session(session)?;
+            self.last_session_id = Some(id);
         }
         Ok(())
     }
@@ -561,12 +1989,98 @@ impl App {
         &self.storage
     }
 
+    /// Handle panel switching and heatmap actions in the stats view
+    ///
+    /// Returns `true` if the action was consumed.
+    fn handle_stats_action(&mut self, action: &Action) -> bool {
+        let toggled = match action {
+            Action::Heatmap => Some(StatsPanel::Heatmap),
+            Action::Distribution => Some(StatsPanel::Distribution),
+            Action::Estimates => Some(StatsPanel::Estimates),
+            _ => None,
+        };
+        if let Some(panel) = toggled {
+            // Pressing a panel's key again goes back to the overview
+            self.stats_panel = if self.stats_panel == panel { StatsPanel::Overview } else { panel };
+            self.heatmap_selected = Local::now().date_naive();
+            return true;
+        }
+        if *action == Action::Cancel && self.stats_panel != StatsPanel::Overview {
+            self.stats_panel = StatsPanel::Overview;
+            return true;
+        }
+        if self.stats_panel == StatsPanel::Overview {
+            // Scroll the chart back through earlier periods
+            match action {
+                Action::PageUp => self.stats_chart_offset += 1,
+                Action::PageDown => self.stats_chart_offset = self.stats_chart_offset.saturating_sub(1),
+                _ => return false,
+            }
+            return true;
+        }
+        if self.stats_panel != StatsPanel::Heatmap {
+            return false;
+        }
+
+        let days = match action {
+            Action::ScrollUp => -1,
+            Action::ScrollDown => 1,
+            Action::CursorLeft => -7,
+            Action::CursorRight => 7,
+            _ => return false,
+        };
+        let heatmap = self.heatmap();
+        let date = self.heatmap_selected + chrono::Duration::days(days);
+        if heatmap.contains(date) {
+            self.heatmap_selected = date;
+        }
+        true
+    }
+
+    /// Estimation accuracy of planned tasks finished within the current filter
+    pub fn estimate_report(&self) -> EstimateReport {
+        let range = self.session_filter.range(Local::now().date_naive());
+        EstimateReport::new(&self.plan, &self.sessions_cache)
+            .between(range.map(|(from, _)| from), range.map(|(_, to)| to))
+    }
+
+    /// Daily focus over the past year, for the stats heatmap
+    pub fn heatmap(&self) -> Heatmap {
+        // Same conversion as `tomatocrab stats --heatmap`, whatever the timer length
+        let goals = self.goal.weekly_secs(DEFAULT_POMODORO_SECS);
+        Heatmap::new(&self.sessions_cache, Local::now().date_naive(), goals)
+    }
+
+    /// Today's goal and progress toward it, in the goal's unit
+    pub fn goal_progress(&self) -> (u32, GoalTarget) {
+        let today = Local::now().date_naive();
+        let target = self.goal.target(today.weekday());
+        let totals = goal::day_totals(&self.sessions_cache)
+            .remove(&today)
+            .unwrap_or_default();
+        (target.achieved(totals), target)
+    }
+
+    /// Current and longest streaks of days meeting the goal
+    pub fn streaks(&self) -> Streaks {
+        self.goal.streaks(&self.sessions_cache, Local::now().date_naive())
+    }
+
+    /// Focus time and completion rate by hour and weekday over the filtered sessions
+    pub fn distribution(&self) -> Distribution {
+        Distribution::from_sessions(self.filtered_sessions())
+    }
+
+    /// Sessions on the day selected in the heatmap, oldest first
+    pub fn heatmap_sessions(&self) -> Vec<&Session> {
+        self.sessions_cache
+            .iter()
+            .filter(|session| session.started_at.with_timezone(&Local).date_naive() == self.heatmap_selected)
+            .collect()
+    }
+
     /// Get filter label
     pub fn filter_label(&self) -> &'static str {
-        match self.session_filter {
-            SessionFilter::Today => "Today",
-            SessionFilter::Week => "This Week",
-            SessionFilter::All => "All Time",
-        }
+        self.session_filter.label()
     }
 }
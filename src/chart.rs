@@ -0,0 +1,144 @@
+//! Focus-over-time chart whose time scale follows the active filter
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+
+use crate::components::session_list::SessionFilter;
+use crate::session::Session;
+
+/// Size of each bar in the focus chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartScale {
+    /// One bar per day over seven days
+    Days,
+    /// One bar per week of a calendar month
+    Weeks,
+    /// One bar per month of a calendar year
+    Months,
+}
+
+impl ChartScale {
+    /// Scale matching a filter: days for a week, weeks for a month, months for a year
+    pub fn for_filter(filter: SessionFilter) -> Self {
+        match filter {
+            SessionFilter::Today | SessionFilter::Week => ChartScale::Days,
+            SessionFilter::Month => ChartScale::Weeks,
+            SessionFilter::Year | SessionFilter::All => ChartScale::Months,
+        }
+    }
+
+    /// Unit the bar values are shown in
+    pub fn unit(&self) -> &'static str {
+        match self {
+            ChartScale::Days | ChartScale::Weeks => "minutes",
+            ChartScale::Months => "hours",
+        }
+    }
+}
+
+/// One bar of the focus chart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChartBar {
+    pub label: String,
+    pub focus_secs: u64,
+}
+
+impl ChartBar {
+    /// Bar height in the chart's unit
+    pub fn value(&self, scale: ChartScale) -> u64 {
+        match scale {
+            ChartScale::Days | ChartScale::Weeks => self.focus_secs / 60,
+            ChartScale::Months => self.focus_secs / 3600,
+        }
+    }
+}
+
+/// Focus time per day, week or month over one period
+#[derive(Debug, Clone)]
+pub struct FocusChart {
+    pub scale: ChartScale,
+    /// Name of the period shown, e.g. "October 2026"
+    pub title: String,
+    pub bars: Vec<ChartBar>,
+}
+
+impl FocusChart {
+    /// Chart of the period `offset` periods before the one containing `today`
+    pub fn new<'a>(
+        sessions: impl IntoIterator<Item = &'a Session> + Copy,
+        scale: ChartScale,
+        today: NaiveDate,
+        offset: u32,
+    ) -> Self {
+        let focus = |from: NaiveDate, to: NaiveDate| -> u64 {
+            sessions
+                .into_iter()
+                .filter(|session| (from..=to).contains(&session.started_at.with_timezone(&Local).date_naive()))
+                .map(|session| session.duration_secs as u64)
+                .sum()
+        };
+
+        let (title, buckets) = match scale {
+            ChartScale::Days => {
+                let end = today - Duration::weeks(offset as i64);
+                let start = end - Duration::days(6);
+                let title = if offset == 0 {
+                    "Past 7 Days".to_string()
+                } else {
+                    format!("{} – {}", start.format("%b %-d"), end.format("%b %-d"))
+                };
+                let buckets = (0..7)
+                    .map(|day| {
+                        let date = start + Duration::days(day);
+                        (date.format("%a").to_string(), date, date)
+                    })
+                    .collect();
+                (title, buckets)
+            }
+            ChartScale::Weeks => {
+                let first = today
+                    .with_day(1)
+                    .and_then(|first| first.checked_sub_months(Months::new(offset)))
+                    .unwrap_or(today);
+                let last = first
+                    .checked_add_months(Months::new(1))
+                    .map_or(first, |next| next - Duration::days(1));
+                // Calendar weeks from Monday, cut off at the month's edges
+                let mut buckets = Vec::new();
+                let mut start = first;
+                while start <= last {
+                    let sunday = start + Duration::days(6 - start.weekday().num_days_from_monday() as i64);
+                    let end = sunday.min(last);
+                    let label = if start == end {
+                        start.day().to_string()
+                    } else {
+                        format!("{}-{}", start.day(), end.day())
+                    };
+                    buckets.push((label, start, end));
+                    start = end + Duration::days(1);
+                }
+                (first.format("%B %Y").to_string(), buckets)
+            }
+            ChartScale::Months => {
+                let year = today.year() - offset as i32;
+                let buckets = (1..=12)
+                    .filter_map(|month| {
+                        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                        let last = first.checked_add_months(Months::new(1))? - Duration::days(1);
+                        Some((first.format("%b").to_string(), first, last))
+                    })
+                    .collect();
+                (year.to_string(), buckets)
+            }
+        };
+
+        let bars = buckets
+            .into_iter()
+            .map(|(label, from, to)| ChartBar {
+                label,
+                focus_secs: focus(from, to),
+            })
+            .collect();
+
+        Self { scale, title, bars }
+    }
+}
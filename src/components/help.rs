@@ -0,0 +1,114 @@
+//! Overlay listing the keys that do something in the current view and timer state
+
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::app::{App, View};
+use crate::keymap::{Context, Key, COMMANDS};
+use crate::theme::Theme;
+
+/// Width of the key column
+const KEY_WIDTH: usize = 12;
+
+/// Widget for the key help overlay
+pub struct HelpWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> HelpWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Self { app }
+    }
+
+    /// Render the overlay centered over `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let context = self.app.key_context();
+        let timer_actions = self.app.timer_actions();
+
+        let mut left = Vec::new();
+        if context != Context::Timer {
+            self.push_section(&mut left, context, |_| true);
+        }
+        self.push_section(&mut left, Context::Timer, |action| timer_actions.contains(action));
+        // Filtering only applies to the views that list sessions
+        let filters = matches!(self.app.current_view, View::History | View::Stats);
+        let mut right = Vec::new();
+        self.push_section(&mut right, Context::Global, |action| {
+            filters || *action != Action::CycleFilter
+        });
+
+        let width = area.width.min(100);
+        let height = area.height.min(left.len().max(right.len()) as u16 + 4);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(" Keys ")
+            .title_style(Theme::title())
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let [body, footer] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+        frame.render_widget(Paragraph::new(left), left_area);
+        frame.render_widget(Paragraph::new(right), right_area);
+
+        let palette = self.app.keymap.keys(context, &Action::OpenPalette);
+        let mut hint = vec![Span::styled("Press any key to close", Theme::muted())];
+        if let Some(key) = palette.first() {
+            hint.push(Span::styled("  ·  ", Theme::muted()));
+            hint.push(Span::styled(format!("[{}]", key), Theme::key_hint()));
+            hint.push(Span::styled(" runs any command by name", Theme::muted()));
+        }
+        frame.render_widget(Paragraph::new(Line::from(hint)).alignment(Alignment::Center), footer);
+    }
+
+    /// Add a heading and a line per bound command of `context` that passes `show`
+    fn push_section(&self, lines: &mut Vec<Line<'static>>, context: Context, show: impl Fn(&Action) -> bool) {
+        let rows: Vec<Line> = COMMANDS
+            .iter()
+            .filter(|command| command.context == context && show(&command.action))
+            .filter_map(|command| {
+                let keys = self.app.keymap.command_keys(command);
+                if keys.is_empty() {
+                    return None;
+                }
+                let keys: Vec<String> = keys.iter().map(Key::to_string).collect();
+                Some(Line::from(vec![
+                    Span::styled(format!(" {:<width$} ", keys.join(" "), width = KEY_WIDTH), Theme::key_hint()),
+                    Span::styled(command.description, Theme::key_action()),
+                ]))
+            })
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        let title = match context {
+            Context::Global => "Everywhere",
+            Context::Timer => "Timer",
+            Context::Plan => "Plan",
+            Context::History => "History",
+            Context::Stats => "Stats",
+        };
+        lines.push(Line::styled(title, Theme::subtitle()));
+        lines.extend(rows);
+    }
+}
@@ -0,0 +1,61 @@
+//! Centered bar of key hints whose chips can also be clicked
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::action::Action;
+use crate::app::App;
+use crate::theme::Theme;
+
+/// A hint chip: the keys to press, what they do, and the action a click runs
+pub struct Hint<'a> {
+    pub keys: String,
+    pub label: &'a str,
+    /// `Action::None` when the chip only explains keys, e.g. `1-5` to rate
+    pub action: Action,
+}
+
+impl<'a> Hint<'a> {
+    pub fn new(keys: impl Into<String>, label: &'a str, action: Action) -> Self {
+        Self {
+            keys: keys.into(),
+            label,
+            action,
+        }
+    }
+}
+
+/// Render `prefix` then `hints` centered in `area`, making each chip clickable
+pub fn render_hints(frame: &mut Frame, area: Rect, app: &App, prefix: Vec<Span>, hints: &[Hint]) {
+    let mut spans = prefix;
+    let mut chips = Vec::new();
+    for (i, hint) in hints.iter().enumerate() {
+        let start: usize = spans.iter().map(Span::width).sum();
+        spans.push(Span::styled(format!("[{}]", hint.keys), Theme::key_hint()));
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(hint.label, Theme::key_action()));
+        let end: usize = spans.iter().map(Span::width).sum();
+        chips.push((start, end, &hint.action));
+        if i < hints.len() - 1 {
+            spans.push(Span::raw("  "));
+        }
+    }
+
+    let line = Line::from(spans);
+    // Same offset the centered paragraph uses
+    let offset = (area.width / 2).saturating_sub(line.width() as u16 / 2);
+    let mut mouse = app.mouse.borrow_mut();
+    for (start, end, action) in chips {
+        if *action == Action::None {
+            continue;
+        }
+        let chip = Rect::new(area.x + offset + start as u16, area.y, (end - start) as u16, 1);
+        mouse.click(chip.intersection(area), action.clone());
+    }
+
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
+}
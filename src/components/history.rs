@@ -1,16 +1,22 @@
 //! History view widget displaying past sessions in a table
 
-use chrono::Local;
+use chrono::{Local, NaiveDate};
 use ratatui::{
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Rect},
     style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
-use crate::app::App;
-use crate::theme::{Theme, BORDER, PRIMARY};
+use crate::action::Action;
+use crate::app::{App, HistoryRow, SessionEdit};
+use crate::components::hints::{render_hints, Hint};
+use crate::components::session_list::SessionStats;
+use crate::keymap::Context;
+use crate::session::Session;
+use crate::text::truncate;
+use crate::theme::Theme;
 
 /// Widget for displaying session history
 pub struct HistoryWidget<'a> {
@@ -24,18 +30,72 @@ impl<'a> HistoryWidget<'a> {
 
     /// Render the history widget
     pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let show_search = self.app.history_jump.is_some()
+            || self.app.history_searching
+            || !self.app.history_search.text().is_empty();
+        let search_height = if show_search { 3 } else { 0 };
+        let detail_height = if self.app.history_detail { 16 } else { 0 };
         let chunks = Layout::vertical([
-            Constraint::Min(5),     // Table
-            Constraint::Length(2),  // Hints
+            Constraint::Length(search_height),  // Search query
+            Constraint::Min(5),                 // Table
+            Constraint::Length(detail_height),  // Selected session details
+            Constraint::Length(2),              // Hints
         ])
         .split(area);
 
-        self.render_table(frame, chunks[0]);
-        self.render_hints(frame, chunks[1]);
+        if self.app.history_jump.is_some() {
+            self.render_jump(frame, chunks[0]);
+        } else if show_search {
+            self.render_search(frame, chunks[0]);
+        }
+        self.render_table(frame, chunks[1]);
+        if self.app.history_detail {
+            self.render_detail(frame, chunks[2]);
+        }
+        self.render_hints(frame, chunks[3]);
+    }
+
+    fn render_search(&self, frame: &mut Frame, area: Rect) {
+        let searching = self.app.history_searching;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(if searching { Theme::border_active() } else { Theme::border() })
+            .title(" Search ")
+            .title_style(if searching { Theme::subtitle() } else { Theme::muted() });
+        let inner = block.inner(area);
+
+        let (visible, cursor) = self.app.history_search.view(inner.width as usize);
+        frame.render_widget(Paragraph::new(visible).style(Theme::bright()).block(block), area);
+        if searching {
+            frame.set_cursor_position((inner.x + cursor as u16, inner.y));
+        }
+    }
+
+    fn render_jump(&self, frame: &mut Frame, area: Rect) {
+        let Some(jump) = &self.app.history_jump else {
+            return;
+        };
+
+        let (title, title_style) = if jump.invalid {
+            (" Not a date - use YYYY-MM-DD, today or yesterday ", Theme::warning())
+        } else {
+            (" Jump to Date (YYYY-MM-DD, today, yesterday) ", Theme::subtitle())
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(title)
+            .title_style(title_style);
+        let inner = block.inner(area);
+
+        let (visible, cursor) = jump.editor.view(inner.width as usize);
+        frame.render_widget(Paragraph::new(visible).style(Theme::bright()).block(block), area);
+        frame.set_cursor_position((inner.x + cursor as u16, inner.y));
     }
 
     fn render_table(&self, frame: &mut Frame, area: Rect) {
-        let filtered = self.app.filtered_sessions();
+        let history_rows = self.app.history_rows();
+        let grouped = self.app.history_grouped;
 
         // Create header
         let header_cells = ["Date", "Time", "Task", "Duration", "Status"]
@@ -45,14 +105,21 @@ impl<'a> HistoryWidget<'a> {
             });
         let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-        // Create rows (reversed to show most recent first)
-        let rows: Vec<Row> = filtered
+        let rows: Vec<Row> = history_rows
             .iter()
-            .rev()
             .enumerate()
-            .map(|(idx, session)| {
+            .map(|(idx, row)| {
+                let session = match row {
+                    HistoryRow::Day { date, sessions } => return self.day_row(*date, sessions),
+                    HistoryRow::Session(session) => session,
+                };
                 let local_time = session.started_at.with_timezone(&Local);
-                let date = local_time.format("%Y-%m-%d").to_string();
+                // The day heading already shows the date
+                let date = if grouped {
+                    String::new()
+                } else {
+                    local_time.format("%Y-%m-%d").to_string()
+                };
                 let time = local_time.format("%H:%M").to_string();
                 let duration = format_duration(session.duration_secs);
 
@@ -63,13 +130,9 @@ impl<'a> HistoryWidget<'a> {
                 };
 
                 // Truncate task if too long
-                let task = if session.task.len() > 30 {
-                    format!("{}...", &session.task[..27])
-                } else {
-                    session.task.clone()
-                };
+                let task = truncate(&session.task, 30);
 
-                let row_style = if idx == self.selected_index(&filtered) {
+                let row_style = if idx == self.app.history_selected {
                     Theme::table_row_selected()
                 } else {
                     Theme::table_row()
@@ -86,7 +149,20 @@ impl<'a> HistoryWidget<'a> {
             })
             .collect();
 
-        let title = format!(" Session History ({}) ", self.app.filter_label());
+        let arrow = if self.app.history_sort_ascending { "↑" } else { "↓" };
+        let mut title = format!(
+            " Session History ({}) - Sorted by {} {} ",
+            self.app.filter_label(),
+            self.app.history_sort.label(),
+            arrow
+        );
+        if grouped {
+            title.push_str("- By Day ");
+        }
+        if !self.app.history_search.text().is_empty() {
+            let matching = self.app.history_sessions().len();
+            title.push_str(&format!("- {} matching ", matching));
+        }
 
         let table = Table::new(
             rows,
@@ -102,11 +178,11 @@ impl<'a> HistoryWidget<'a> {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(ratatui::style::Style::default().fg(BORDER))
+                .border_style(ratatui::style::Style::default().fg(Theme::current().border))
                 .title(title)
                 .title_style(
                     ratatui::style::Style::default()
-                        .fg(PRIMARY)
+                        .fg(Theme::current().primary)
                         .add_modifier(Modifier::BOLD),
                 ),
         )
@@ -114,49 +190,206 @@ impl<'a> HistoryWidget<'a> {
 
         // Render with selection state
         let mut state = TableState::default();
-        state.select(Some(self.selected_index(&filtered)));
+        if !history_rows.is_empty() {
+            state.select(Some(self.app.history_selected));
+        }
         frame.render_stateful_widget(table, area, &mut state);
+
+        // Rows are one line each below the header, scrolled to keep the selection in view
+        let mut mouse = self.app.mouse.borrow_mut();
+        mouse.scroll(area);
+        let body = Block::default().borders(Borders::ALL).inner(area);
+        let first_row = body.y + 2;
+        for (line, row) in (first_row..body.bottom()).zip(state.offset()..history_rows.len()) {
+            mouse.click(Rect::new(body.x, line, body.width, 1), Action::SelectRow(row));
+        }
     }
 
-    fn selected_index(&self, filtered: &[&crate::session::Session]) -> usize {
-        // Reverse the index since we display in reverse order
-        let len = filtered.len();
-        if len == 0 {
-            0
-        } else {
-            len.saturating_sub(1).saturating_sub(self.app.history_selected)
+    /// Heading row for a day with its completed/interrupted counts and focus time
+    fn day_row(&self, date: NaiveDate, sessions: &[&Session]) -> Row<'static> {
+        let stats = SessionStats::from_sessions(sessions.iter().copied());
+        let marker = if self.app.history_collapsed.contains(&date) { "▶" } else { "▼" };
+
+        Row::new(vec![
+            Cell::from(format!("{} {}", marker, date.format("%Y-%m-%d"))),
+            Cell::from(date.format("%a").to_string()),
+            Cell::from(format!(
+                "{} completed, {} interrupted",
+                stats.completed_sessions, stats.interrupted_sessions
+            )),
+            Cell::from(format_duration(stats.total_focus_time_secs)),
+            Cell::from(""),
+        ])
+        .style(Theme::subtitle())
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect) {
+        let Some(session) = self.app.selected_session() else {
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(" Session Details ")
+            .title_style(Theme::subtitle());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),     // Details
+            Constraint::Length(if self.app.history_edit.is_some() { 3 } else { 0 }),
+        ])
+        .split(inner);
+
+        let start = session.started_at.with_timezone(&Local);
+        let end = session.ended_at().with_timezone(&Local);
+        let duration = match session.planned_secs {
+            Some(planned) if planned > 0 => format!(
+                "{} of {} planned ({:.0}%)",
+                format_duration(session.duration_secs),
+                format_duration(planned),
+                session.duration_secs as f64 / planned as f64 * 100.0
+            ),
+            _ => format_duration(session.duration_secs),
+        };
+        let status = match (session.completed, session.manual) {
+            (true, false) => "Completed",
+            (false, false) => "Interrupted",
+            (true, true) => "Completed (logged manually)",
+            (false, true) => "Interrupted (logged manually)",
+        };
+        let tags = session.tags();
+        let tags = (!tags.is_empty()).then(|| {
+            tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" ")
+        });
+        let pauses = (!session.pauses.is_empty()).then(|| {
+            let list: Vec<String> = session
+                .pauses
+                .iter()
+                .map(|pause| {
+                    format!(
+                        "{} for {}",
+                        pause.started_at.with_timezone(&Local).format("%H:%M:%S"),
+                        format_duration(pause.duration_secs)
+                    )
+                })
+                .collect();
+            format!("{} total: {}", format_duration(session.paused_secs()), list.join(", "))
+        });
+        let focus = session.focus.map(|focus| {
+            format!(
+                "{}{} {}/5",
+                "●".repeat(focus as usize),
+                "○".repeat(5_usize.saturating_sub(focus as usize)),
+                focus
+            )
+        });
+
+        let lines = vec![
+            field("Task:     ", Some(&session.task)),
+            field("Start:    ", Some(&start.format("%A %Y-%m-%d %H:%M:%S").to_string())),
+            field("End:      ", Some(&end.format("%A %Y-%m-%d %H:%M:%S").to_string())),
+            field("Duration: ", Some(&duration)),
+            field("Status:   ", Some(status)),
+            field("Tags:     ", tags.as_deref()),
+            field("Pauses:   ", pauses.as_deref()),
+            field("Done:     ", session.accomplished.as_deref()),
+            field("Focus:    ", focus.as_deref()),
+            field("Notes:    ", session.notes.as_deref()),
+            field("ID:       ", Some(&session.id.to_string())),
+        ];
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        if let Some(edit) = &self.app.history_edit {
+            let title = match edit {
+                SessionEdit::Task(_) => " Edit Task ",
+                SessionEdit::Notes(_) => " Edit Notes ",
+            };
+            let edit_block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(ratatui::style::Style::default().fg(Theme::current().highlight))
+                .title(title)
+                .title_style(Theme::subtitle());
+            let edit_inner = edit_block.inner(chunks[1]);
+
+            let (visible, cursor) = edit.editor().view(edit_inner.width as usize);
+            frame.render_widget(Paragraph::new(visible).style(Theme::bright()).block(edit_block), chunks[1]);
+            frame.set_cursor_position((edit_inner.x + cursor as u16, edit_inner.y));
         }
     }
 
     fn render_hints(&self, frame: &mut Frame, area: Rect) {
-        let hints = vec![
-            ("Tab", "Switch View"),
-            ("f", "Filter"),
-            ("Up/Down", "Navigate"),
-            ("q", "Quit"),
-        ];
+        if self.app.history_confirm_delete {
+            let prefix = vec![Span::styled("Delete this session? ", Theme::warning())];
+            let hints = [
+                Hint::new("y", "Delete", Action::Input('y')),
+                Hint::new("any key", "Keep", Action::Cancel),
+            ];
+            render_hints(frame, area, self.app, prefix, &hints);
+            return;
+        }
 
-        let hint_spans: Vec<Span> = hints
-            .iter()
-            .enumerate()
-            .flat_map(|(i, (key, action))| {
-                let mut spans = vec![
-                    Span::styled(format!("[{}]", key), Theme::key_hint()),
-                    Span::raw(" "),
-                    Span::styled(*action, Theme::key_action()),
-                ];
-                if i < hints.len() - 1 {
-                    spans.push(Span::raw("  "));
-                }
-                spans
-            })
-            .collect();
+        let on_day = matches!(
+            self.app.history_rows().get(self.app.history_selected),
+            Some(HistoryRow::Day { .. })
+        );
+        // Prompts take typed text, so only Enter and Esc act on them
+        let prompt = |confirm| {
+            vec![
+                Hint::new("Enter", confirm, Action::Confirm),
+                Hint::new("Esc", "Cancel", Action::Cancel),
+            ]
+        };
+        let hints = match (self.app.history_detail, &self.app.history_edit) {
+            _ if self.app.history_jump.is_some() => prompt("Jump"),
+            _ if self.app.history_searching => vec![
+                Hint::new("Enter", "Keep Results", Action::Confirm),
+                Hint::new("Esc", "Clear Search", Action::Cancel),
+            ],
+            (_, Some(_)) => prompt("Save"),
+            (true, None) => self.app.keymap.hints(
+                Context::History,
+                &[
+                    (&[Action::Confirm], "Close"),
+                    (&[Action::EditTask], "Edit Task"),
+                    (&[Action::EditNotes], "Edit Notes"),
+                    (&[Action::DeleteSelected], "Delete"),
+                    (&[Action::ScrollUp, Action::ScrollDown], "Navigate"),
+                    (&[Action::Quit], "Quit"),
+                ],
+            ),
+            (false, None) => self.app.keymap.hints(
+                Context::History,
+                &[
+                    (&[Action::NextTab], "Switch View"),
+                    (&[Action::CycleFilter], "Filter"),
+                    (&[Action::Search], "Search"),
+                    (&[Action::Sort, Action::ReverseSort], "Sort"),
+                    (&[Action::Group], "Group"),
+                    (&[Action::JumpToDate], "Jump"),
+                    (&[Action::Confirm], if on_day { "Fold" } else { "Details" }),
+                    (&[Action::ShowHelp], "Help"),
+                    (&[Action::Quit], "Quit"),
+                ],
+            ),
+        };
 
-        let hints_paragraph = Paragraph::new(Line::from(hint_spans)).alignment(Alignment::Center);
-        frame.render_widget(hints_paragraph, area);
+        render_hints(frame, area, self.app, Vec::new(), &hints);
     }
 }
 
+/// A labelled line of the detail pane, with a dash for missing values
+fn field<'a>(label: &'static str, value: Option<&str>) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(label, Theme::stat_label()),
+        match value {
+            Some(value) => Span::styled(value.to_string(), Theme::bright()),
+            None => Span::styled("-", Theme::muted()),
+        },
+    ])
+}
+
 /// Format duration in seconds to human readable format
 fn format_duration(secs: u32) -> String {
     let minutes = secs / 60;
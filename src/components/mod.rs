@@ -3,10 +3,19 @@ pub mod task_input;
 pub mod session_list;
 pub mod tabs;
 pub mod history;
+pub mod plan;
+pub mod retro;
+pub mod help;
+pub mod hints;
+pub mod palette;
 pub mod stats;
 
 pub use timer::TimerWidget;
 pub use task_input::TaskInputWidget;
 pub use tabs::TabsWidget;
 pub use history::HistoryWidget;
+pub use plan::PlanWidget;
+pub use retro::RetroWidget;
+pub use help::HelpWidget;
+pub use palette::PaletteWidget;
 pub use stats::StatsWidget;
@@ -0,0 +1,106 @@
+//! Command palette popup with the commands matching what has been typed
+
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+use crate::palette;
+use crate::text::truncate;
+use crate::theme::Theme;
+
+/// Most matches listed at once
+const MAX_MATCHES: usize = 8;
+
+/// Width of the command name column
+const NAME_WIDTH: usize = 12;
+
+/// Widget for the command palette
+pub struct PaletteWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> PaletteWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Self { app }
+    }
+
+    /// Render the palette near the top of `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(state) = &self.app.palette else {
+            return;
+        };
+        let matches = palette::matches(state.editor.text(), self.app.key_context());
+        // Keep the highlighted match in the listed window
+        let first = state.selected.saturating_sub(MAX_MATCHES - 1);
+        let shown = matches.len().saturating_sub(first).min(MAX_MATCHES);
+
+        let message = state.error.is_some() as u16;
+        let width = area.width.min(80);
+        let height = area.height.min(3 + shown.max(1) as u16 + message + 1);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + 1,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(" Command ")
+            .title_style(Theme::title())
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let [input_area, list_area, message_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(message),
+        ])
+        .areas(inner);
+
+        let (visible, cursor) = state.editor.view(input_area.width.saturating_sub(2) as usize);
+        let input = Line::from(vec![Span::styled(": ", Theme::key_hint()), Span::styled(visible, Theme::bright())]);
+        frame.render_widget(Paragraph::new(input), input_area);
+        frame.set_cursor_position((input_area.x + 2 + cursor as u16, input_area.y));
+
+        let lines: Vec<Line> = if matches.is_empty() {
+            vec![Line::styled(" No matching commands", Theme::muted())]
+        } else {
+            matches
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(shown)
+                .map(|(i, command)| {
+                    let usage = match command.usage() {
+                        "" => String::new(),
+                        usage => format!("{} ", usage),
+                    };
+                    let room = (list_area.width as usize).saturating_sub(NAME_WIDTH + 2 + usage.len());
+                    let line = Line::from(vec![
+                        Span::styled(format!(" {:<width$} ", command.name(), width = NAME_WIDTH), Theme::bright()),
+                        Span::styled(usage, Theme::key_hint()),
+                        Span::styled(truncate(command.description(), room), Theme::muted()),
+                    ]);
+                    if i == state.selected {
+                        line.style(Theme::table_row_selected())
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), list_area);
+
+        if let Some(error) = &state.error {
+            frame.render_widget(Paragraph::new(Line::styled(error.as_str(), Theme::warning())), message_area);
+        }
+    }
+}
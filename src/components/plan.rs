@@ -0,0 +1,161 @@
+//! Plan view widget listing today's planned tasks in order
+
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Modifier,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::app::App;
+use crate::components::hints::{render_hints, Hint};
+use crate::keymap::Context;
+use crate::text::truncate;
+use crate::theme::Theme;
+
+/// Widget for displaying and editing the day's plan
+pub struct PlanWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> PlanWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Self { app }
+    }
+
+    /// Render the plan widget
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([
+            Constraint::Min(5),     // Table
+            Constraint::Length(3),  // New task input
+            Constraint::Length(2),  // Hints
+        ])
+        .split(area);
+
+        self.render_table(frame, chunks[0]);
+        self.render_input(frame, chunks[1]);
+        self.render_hints(frame, chunks[2]);
+    }
+
+    fn render_table(&self, frame: &mut Frame, area: Rect) {
+        let today = Local::now().date_naive();
+        let rows_idx = self.app.plan_rows();
+        // Whatever the fixed columns, borders and column gaps leave over
+        let title_width = (area.width as usize).saturating_sub(4 + 11 + 12 + 2 + 3);
+
+        let header_cells = ["#", "Task", "Pomodoros", "Status"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Theme::table_header()));
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows: Vec<Row> = rows_idx
+            .iter()
+            .enumerate()
+            .map(|(pos, &i)| {
+                let task = &self.app.plan.tasks[i];
+                let done = self.app.plan_task_pomodoros(task);
+                let pomodoros = match task.estimate {
+                    Some(estimate) => format!("{}/{}", done, estimate),
+                    None => format!("{}/?", done),
+                };
+
+                let status_cell = if task.done {
+                    Cell::from("Done").style(Theme::status_completed())
+                } else if task.date < today {
+                    Cell::from(format!("From {}", task.date.format("%m-%d")))
+                        .style(Theme::status_interrupted())
+                } else {
+                    Cell::from("Open").style(Theme::muted())
+                };
+
+                let row_style = if task.done { Theme::muted() } else { Theme::table_row() };
+
+                Row::new(vec![
+                    Cell::from(format!("{}", pos + 1)),
+                    Cell::from(truncate(&task.title, title_width)),
+                    Cell::from(pomodoros),
+                    status_cell,
+                ])
+                .style(row_style)
+            })
+            .collect();
+
+        let title = format!(" Plan for {} ", today.format("%A %Y-%m-%d"));
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),   // Position
+                Constraint::Min(20),     // Task
+                Constraint::Length(11),  // Pomodoros
+                Constraint::Length(12),  // Status
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+                .title(title)
+                .title_style(
+                    ratatui::style::Style::default()
+                        .fg(Theme::current().primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        )
+        .row_highlight_style(Theme::table_row_selected());
+
+        let mut state = TableState::default();
+        if !rows_idx.is_empty() {
+            state.select(Some(self.app.plan_selected));
+        }
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn render_input(&self, frame: &mut Frame, area: Rect) {
+        let Some(input) = &self.app.plan_input else {
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(" New Task ")
+            .title_style(Theme::title());
+        let inner = block.inner(area);
+
+        let (visible, cursor) = input.view(inner.width as usize);
+        let paragraph = Paragraph::new(visible).style(Theme::bright()).block(block);
+        frame.render_widget(paragraph, area);
+        frame.set_cursor_position((inner.x + cursor as u16, inner.y));
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let hints = if self.app.plan_input.is_some() {
+            vec![
+                Hint::new("Enter", "Add", Action::Confirm),
+                Hint::new("Esc", "Cancel", Action::Cancel),
+            ]
+        } else {
+            self.app.keymap.hints(
+                Context::Plan,
+                &[
+                    (&[Action::AddTask], "Add"),
+                    (&[Action::Confirm], "Start"),
+                    (&[Action::IncreaseEstimate, Action::DecreaseEstimate], "Estimate"),
+                    (&[Action::ToggleDone], "Done"),
+                    (&[Action::MoveDown, Action::MoveUp], "Move"),
+                    (&[Action::CarryOver], "Tomorrow"),
+                    (&[Action::DeleteSelected], "Delete"),
+                    (&[Action::NextTab], "Switch View"),
+                    (&[Action::ShowHelp], "Help"),
+                    (&[Action::Quit], "Quit"),
+                ],
+            )
+        };
+
+        render_hints(frame, area, self.app, Vec::new(), &hints);
+    }
+}
@@ -0,0 +1,137 @@
+//! Retrospective popup shown after a work session completes
+
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::app::{App, RetroField, RetroForm};
+use crate::components::hints::{render_hints, Hint};
+use crate::editor::LineEditor;
+use crate::theme::Theme;
+
+/// Widget for the quick retrospective form
+pub struct RetroWidget<'a> {
+    app: &'a App,
+}
+
+impl<'a> RetroWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Self { app }
+    }
+
+    /// Render the form centered over `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(form) = &self.app.retro else {
+            return;
+        };
+
+        let width = area.width.min(64);
+        let height = area.height.min(15);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border_active())
+            .title(" How did it go? ")
+            .title_style(Theme::title())
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3),  // What got done
+            Constraint::Length(3),  // Focus rating
+            Constraint::Length(3),  // Notes
+            Constraint::Min(0),     // Spacer
+            Constraint::Length(2),  // Hints
+        ])
+        .split(inner);
+
+        self.render_editor(frame, chunks[0], form, RetroField::Accomplished, " What got done ", &form.accomplished);
+        self.render_focus(frame, chunks[1], form);
+        self.render_editor(frame, chunks[2], form, RetroField::Notes, " Notes ", &form.notes);
+        self.render_hints(frame, chunks[4], form);
+    }
+
+    fn render_editor(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        form: &RetroForm,
+        field: RetroField,
+        title: &str,
+        editor: &LineEditor,
+    ) {
+        let active = form.field == field;
+        let block = field_block(title, active);
+        let inner = block.inner(area);
+
+        let (visible, cursor) = editor.view(inner.width as usize);
+        frame.render_widget(Paragraph::new(visible).style(Theme::bright()).block(block), area);
+        if active {
+            frame.set_cursor_position((inner.x + cursor as u16, inner.y));
+        }
+    }
+
+    fn render_focus(&self, frame: &mut Frame, area: Rect, form: &RetroForm) {
+        let block = field_block(" Focus (1-5) ", form.field == RetroField::Focus);
+
+        let rating = form.focus.unwrap_or(0);
+        let mut spans: Vec<Span> = (1..=5)
+            .map(|n| {
+                if n <= rating {
+                    Span::styled("● ", Theme::highlight())
+                } else {
+                    Span::styled("○ ", Theme::muted())
+                }
+            })
+            .collect();
+        spans.push(Span::styled(focus_label(form.focus), Theme::muted()));
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect, form: &RetroForm) {
+        let mut hints = vec![
+            Hint::new("Enter", "Save", Action::Confirm),
+            Hint::new("Tab", "Next Field", Action::NextTab),
+            Hint::new("Esc", "Skip", Action::Cancel),
+        ];
+        if form.field == RetroField::Focus {
+            hints.insert(1, Hint::new("1-5", "Rate", Action::None));
+        }
+
+        render_hints(frame, area, self.app, Vec::new(), &hints);
+    }
+}
+
+/// Bordered block for a form field, highlighted while it has focus
+fn field_block(title: &str, active: bool) -> Block<'_> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(if active { Theme::border_active() } else { Theme::border() })
+        .title(title)
+        .title_style(if active { Theme::subtitle() } else { Theme::muted() })
+}
+
+/// Description of a focus rating
+fn focus_label(focus: Option<u8>) -> &'static str {
+    match focus {
+        None => " not rated",
+        Some(1) => " distracted",
+        Some(2) => " scattered",
+        Some(3) => " okay",
+        Some(4) => " focused",
+        Some(_) => " deep focus",
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::Local;
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
 use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
@@ -6,6 +6,7 @@ use ratatui::{
     Frame,
 };
 
+use crate::goal::Streaks;
 use crate::session::Session;
 
 /// Filter for session list
@@ -13,9 +14,176 @@ use crate::session::Session;
 pub enum SessionFilter {
     Today,
     Week,
+    /// Since the first of the current month
+    Month,
+    /// Since the first of January
+    Year,
     All,
 }
 
+impl SessionFilter {
+    /// Whether a session falls within this filter, relative to `today`
+    pub fn matches(&self, session: &Session, today: NaiveDate) -> bool {
+        let session_date = session.started_at.with_timezone(&Local).date_naive();
+
+        match self {
+            SessionFilter::Today => session_date == today,
+            SessionFilter::Week => {
+                let week_ago = today - chrono::Duration::days(7);
+                session_date >= week_ago
+            }
+            SessionFilter::Month | SessionFilter::Year => {
+                self.range(today).is_some_and(|(from, _)| session_date >= from)
+            }
+            SessionFilter::All => true,
+        }
+    }
+
+    /// First and last day the filter covers, or `None` for all time
+    pub fn range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let from = match self {
+            SessionFilter::Today => today,
+            SessionFilter::Week => today - Duration::days(7),
+            SessionFilter::Month => today.with_day(1)?,
+            SessionFilter::Year => today.with_ordinal(1)?,
+            SessionFilter::All => return None,
+        };
+        Some((from, today))
+    }
+
+    /// The same stretch of the period before, e.g. the first 10 days of last month
+    pub fn previous_range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let (from, to) = self.range(today)?;
+        let months = match self {
+            SessionFilter::Month => 1,
+            SessionFilter::Year => 12,
+            _ => return shift_back(from, to),
+        };
+        // Days past the end of a shorter month fall back to its last day
+        let back = |date: NaiveDate| date.checked_sub_months(Months::new(months));
+        Some((back(from)?, back(to)?))
+    }
+
+    /// Filter named `today`, `week`, `month`, `year` or `all`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "today" => Some(SessionFilter::Today),
+            "week" => Some(SessionFilter::Week),
+            "month" => Some(SessionFilter::Month),
+            "year" => Some(SessionFilter::Year),
+            "all" => Some(SessionFilter::All),
+            _ => None,
+        }
+    }
+
+    /// Human readable label for the filter
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionFilter::Today => "Today",
+            SessionFilter::Week => "This Week",
+            SessionFilter::Month => "This Month",
+            SessionFilter::Year => "This Year",
+            SessionFilter::All => "All Time",
+        }
+    }
+}
+
+/// A session filter narrowed by an optional date range and tags (used by the CLI)
+#[derive(Debug, Clone)]
+pub struct SessionQuery {
+    /// Preset time window
+    pub filter: SessionFilter,
+    /// First local date to include
+    pub from: Option<NaiveDate>,
+    /// Last local date to include
+    pub to: Option<NaiveDate>,
+    /// Only include sessions carrying at least one of these tags
+    pub tags: Vec<String>,
+}
+
+impl SessionQuery {
+    /// Whether a session matches every part of the query
+    pub fn matches(&self, session: &Session, today: NaiveDate) -> bool {
+        let session_date = session.started_at.with_timezone(&Local).date_naive();
+
+        self.filter.matches(session, today)
+            && self.from.is_none_or(|from| session_date >= from)
+            && self.to.is_none_or(|to| session_date <= to)
+            && (self.tags.is_empty() || {
+                let session_tags = session.tags();
+                self.tags.iter().any(|tag| {
+                    let tag = tag.trim_start_matches('#').to_lowercase();
+                    session_tags.contains(&tag)
+                })
+            })
+    }
+
+    /// Select the sessions matching the query, preserving their order
+    pub fn apply<'a>(&self, sessions: &'a [Session]) -> Vec<&'a Session> {
+        let today = Local::now().date_naive();
+        sessions
+            .iter()
+            .filter(|session| self.matches(session, today))
+            .collect()
+    }
+
+    /// First and last day the query covers, if it is bounded at the start
+    pub fn range(&self, today: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+        let filter_range = self.filter.range(today);
+        let from = match (self.from, filter_range) {
+            (Some(from), Some((filter_from, _))) => from.max(filter_from),
+            (from, filter_range) => from.or(filter_range.map(|(from, _)| from))?,
+        };
+        let to = self.to.map_or(today, |to| to.min(today));
+        Some((from, to))
+    }
+
+    /// The query for the period just before this one, with the same tags
+    ///
+    /// Month and year windows compare against the same days of the previous month or
+    /// year; other ranges against the equally long span right before them.
+    pub fn previous(&self, today: NaiveDate) -> Option<SessionQuery> {
+        let (from, to) = if self.from.is_none() && self.to.is_none() {
+            self.filter.previous_range(today)?
+        } else {
+            let (from, to) = self.range(today)?;
+            shift_back(from, to)?
+        };
+
+        Some(SessionQuery {
+            filter: SessionFilter::All,
+            from: Some(from),
+            to: Some(to),
+            tags: self.tags.clone(),
+        })
+    }
+
+    /// Human readable label describing the query
+    pub fn label(&self) -> String {
+        let mut label = match (self.from, self.to) {
+            (None, None) => self.filter.label().to_string(),
+            (Some(from), Some(to)) => format!("{} to {}", from, to),
+            (Some(from), None) => format!("Since {}", from),
+            (None, Some(to)) => format!("Until {}", to),
+        };
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self
+                .tags
+                .iter()
+                .map(|tag| format!("#{}", tag.trim_start_matches('#')))
+                .collect();
+            label.push_str(&format!(", {}", tags.join(" ")));
+        }
+        label
+    }
+}
+
+/// The equally long span of days ending the day before `from`
+fn shift_back(from: NaiveDate, to: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let days = (to - from).num_days() + 1;
+    Some((from.checked_sub_signed(Duration::days(days))?, to.checked_sub_signed(Duration::days(days))?))
+}
+
 /// Widget for displaying past sessions (TUI view, kept for future use)
 #[allow(dead_code)]
 pub struct SessionListWidget<'a> {
@@ -31,23 +199,11 @@ impl<'a> SessionListWidget<'a> {
 
     /// Filter sessions based on the current filter
     fn filtered_sessions(&self) -> Vec<&Session> {
-        let now = Local::now();
-        let today = now.date_naive();
+        let today = Local::now().date_naive();
 
         self.sessions
             .iter()
-            .filter(|session| {
-                let session_date = session.started_at.with_timezone(&Local).date_naive();
-
-                match self.filter {
-                    SessionFilter::Today => session_date == today,
-                    SessionFilter::Week => {
-                        let week_ago = today - chrono::Duration::days(7);
-                        session_date >= week_ago
-                    }
-                    SessionFilter::All => true,
-                }
-            })
+            .filter(|session| self.filter.matches(session, today))
             .collect()
     }
 
@@ -101,71 +257,169 @@ impl<'a> SessionListWidget<'a> {
     }
 
     fn filter_label(&self) -> &'static str {
-        match self.filter {
-            SessionFilter::Today => "Today",
-            SessionFilter::Week => "This Week",
-            SessionFilter::All => "All Time",
-        }
+        self.filter.label()
     }
 }
 
 /// Format duration in seconds to human readable format
-fn format_duration(secs: u32) -> String {
+pub fn format_duration(secs: u32) -> String {
     let minutes = secs / 60;
     let seconds = secs % 60;
     format!("{}:{:02}", minutes, seconds)
 }
 
-/// Calculate and display statistics
+/// Session counts and focus time over a set of sessions
 pub struct SessionStats {
     pub total_sessions: usize,
     pub completed_sessions: usize,
     pub interrupted_sessions: usize,
+    pub manual_sessions: usize,
     pub total_focus_time_secs: u32,
     pub average_duration_secs: u32,
+    /// Mean self-rated focus over sessions that have a rating
+    pub average_focus: Option<f64>,
+    /// Days in a row the daily goal was met, when known
+    pub streaks: Option<Streaks>,
 }
 
 impl SessionStats {
-    pub fn from_sessions(sessions: &[Session]) -> Self {
+    pub fn from_sessions<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> Self {
+        let sessions: Vec<&Session> = sessions.into_iter().collect();
         let total_sessions = sessions.len();
         let completed_sessions = sessions.iter().filter(|s| s.completed).count();
         let interrupted_sessions = total_sessions - completed_sessions;
+        let manual_sessions = sessions.iter().filter(|s| s.manual).count();
         let total_focus_time_secs: u32 = sessions.iter().map(|s| s.duration_secs).sum();
         let average_duration_secs = if total_sessions > 0 {
             total_focus_time_secs / total_sessions as u32
         } else {
             0
         };
+        let ratings: Vec<u8> = sessions.iter().filter_map(|s| s.focus).collect();
+        let average_focus = (!ratings.is_empty())
+            .then(|| ratings.iter().map(|&r| r as f64).sum::<f64>() / ratings.len() as f64);
 
         Self {
             total_sessions,
             completed_sessions,
             interrupted_sessions,
+            manual_sessions,
             total_focus_time_secs,
             average_duration_secs,
+            average_focus,
+            streaks: None,
         }
     }
 
-    pub fn display(&self) {
-        println!("Session Statistics");
-        println!("==================");
-        println!("Total Sessions:      {}", self.total_sessions);
-        println!("Completed:           {} ({:.1}%)",
+    /// Attach streaks of days meeting the daily goal
+    pub fn with_streaks(mut self, streaks: Streaks) -> Self {
+        self.streaks = Some(streaks);
+        self
+    }
+
+    /// One-line count of completed and interrupted sessions and their focus time
+    pub fn subtotal(&self) -> String {
+        format!(
+            "{} completed, {} interrupted, {} focus",
             self.completed_sessions,
-            if self.total_sessions > 0 {
-                self.completed_sessions as f64 / self.total_sessions as f64 * 100.0
-            } else {
-                0.0
-            }
-        );
-        println!("Interrupted:         {}", self.interrupted_sessions);
-        println!("Total Focus Time:    {}", format_duration_long(self.total_focus_time_secs));
-        println!("Average Duration:    {}", format_duration_long(self.average_duration_secs));
+            self.interrupted_sessions,
+            format_duration_long(self.total_focus_time_secs)
+        )
+    }
+
+    /// Percentage of sessions that ran their full duration
+    pub fn completion_rate(&self) -> f64 {
+        if self.total_sessions > 0 {
+            self.completed_sessions as f64 / self.total_sessions as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Change in a statistic from one period to the next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    /// Current value minus previous value
+    pub change: f64,
+    /// Change relative to the previous value, unless that was zero
+    pub percent: Option<f64>,
+}
+
+impl Delta {
+    pub fn between(previous: f64, current: f64) -> Self {
+        let change = current - previous;
+        let percent = (previous != 0.0).then(|| change / previous * 100.0);
+        Self { change, percent }
+    }
+
+    /// Arrow pointing the way the value moved
+    pub fn arrow(&self) -> &'static str {
+        if self.change > 0.0 {
+            "▲"
+        } else if self.change < 0.0 {
+            "▼"
+        } else {
+            "="
+        }
+    }
+
+    /// Percentage change such as "+12%", or "new" when there was nothing before
+    pub fn percent_label(&self) -> String {
+        match self.percent {
+            Some(percent) => format!("{:+.0}%", percent),
+            None if self.change == 0.0 => "0%".to_string(),
+            None => "new".to_string(),
+        }
+    }
+}
+
+/// Statistics for a period alongside those of the period before it
+pub struct StatsComparison {
+    pub current: SessionStats,
+    pub previous: SessionStats,
+}
+
+impl StatsComparison {
+    pub fn new(current: SessionStats, previous: SessionStats) -> Self {
+        Self { current, previous }
+    }
+
+    pub fn sessions(&self) -> Delta {
+        Delta::between(self.previous.total_sessions as f64, self.current.total_sessions as f64)
+    }
+
+    /// Change in completion rate, in percentage points
+    pub fn completion_rate(&self) -> Delta {
+        Delta::between(self.previous.completion_rate(), self.current.completion_rate())
+    }
+
+    pub fn focus_time(&self) -> Delta {
+        Delta::between(
+            self.previous.total_focus_time_secs as f64,
+            self.current.total_focus_time_secs as f64,
+        )
+    }
+
+    pub fn average_duration(&self) -> Delta {
+        Delta::between(
+            self.previous.average_duration_secs as f64,
+            self.current.average_duration_secs as f64,
+        )
+    }
+}
+
+/// Format a number of days, e.g. "1 day" or "3 days"
+pub fn format_days(days: u32) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
     }
 }
 
 /// Format duration for CLI output
-fn format_duration_long(secs: u32) -> String {
+pub fn format_duration_long(secs: u32) -> String {
     let hours = secs / 3600;
     let minutes = (secs % 3600) / 60;
     let seconds = secs % 60;
@@ -179,55 +433,3 @@ fn format_duration_long(secs: u32) -> String {
     }
 }
 
-/// Display sessions in CLI format
-pub fn display_sessions(sessions: &[Session], filter: SessionFilter) {
-    let now = Local::now();
-    let today = now.date_naive();
-
-    let filtered: Vec<&Session> = sessions
-        .iter()
-        .filter(|session| {
-            let session_date = session.started_at.with_timezone(&Local).date_naive();
-
-            match filter {
-                SessionFilter::Today => session_date == today,
-                SessionFilter::Week => {
-                    let week_ago = today - chrono::Duration::days(7);
-                    session_date >= week_ago
-                }
-                SessionFilter::All => true,
-            }
-        })
-        .collect();
-
-    if filtered.is_empty() {
-        println!("No sessions found.");
-        return;
-    }
-
-    let filter_label = match filter {
-        SessionFilter::Today => "Today",
-        SessionFilter::Week => "This Week",
-        SessionFilter::All => "All Time",
-    };
-
-    println!("Sessions ({})", filter_label);
-    println!("{}", "=".repeat(60));
-    println!("{:<12} {:<8} {:<24} {:<10} {}", "Date", "Time", "Task", "Duration", "Status");
-    println!("{}", "-".repeat(60));
-
-    for session in filtered.iter().rev() {
-        let local_time = session.started_at.with_timezone(&Local);
-        let date = local_time.format("%Y-%m-%d").to_string();
-        let time = local_time.format("%H:%M").to_string();
-        let duration = format_duration(session.duration_secs);
-        let status = if session.completed { "Completed" } else { "Interrupted" };
-        let task = if session.task.len() > 22 {
-            format!("{}...", &session.task[..19])
-        } else {
-            session.task.clone()
-        };
-
-        println!("{:<12} {:<8} {:<24} {:<10} {}", date, time, task, duration, status);
-    }
-}
@@ -1,5 +1,6 @@
 //! Statistics dashboard with stat cards, sparklines, and bar charts
 
+use chrono::Local;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::Modifier,
@@ -8,9 +9,16 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::App;
-use crate::components::session_list::SessionStats;
-use crate::theme::{Theme, ACCENT, BORDER, PRIMARY, SUCCESS, TEXT_BRIGHT};
+use crate::action::Action;
+use crate::app::{App, StatsPanel};
+use crate::components::hints::render_hints;
+use crate::components::session_list::{format_days, Delta, SessionStats, StatsComparison};
+use crate::distribution::{Bucket, WEEKDAYS};
+use crate::estimate;
+use crate::heatmap::WEEKS;
+use crate::keymap::Context;
+use crate::text::pad;
+use crate::theme::Theme;
 
 /// Widget for displaying statistics dashboard
 pub struct StatsWidget<'a> {
@@ -24,6 +32,53 @@ impl<'a> StatsWidget<'a> {
 
     /// Render the stats widget
     pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if self.app.stats_panel == StatsPanel::Distribution {
+            let chunks = Layout::vertical([
+                Constraint::Length(5),  // Stat cards row
+                Constraint::Min(8),     // Hour of day chart
+                Constraint::Length(10), // Weekday table
+                Constraint::Length(2),  // Hints
+            ])
+            .split(area);
+
+            self.render_stat_cards(frame, chunks[0]);
+            self.render_hour_distribution(frame, chunks[1]);
+            self.render_weekday_distribution(frame, chunks[2]);
+            self.render_hints(frame, chunks[3]);
+            return;
+        }
+        if self.app.stats_panel == StatsPanel::Estimates {
+            let weeks = self.app.estimate_report().by_week().len() as u16;
+            let chunks = Layout::vertical([
+                Constraint::Length(5),                    // Stat cards row
+                Constraint::Min(6),                       // Finished tasks
+                Constraint::Length(weeks.clamp(1, 8) + 2), // Weekly trend
+                Constraint::Length(2),                    // Hints
+            ])
+            .split(area);
+
+            self.render_stat_cards(frame, chunks[0]);
+            self.render_estimate_tasks(frame, chunks[1]);
+            self.render_estimate_trend(frame, chunks[2]);
+            self.render_hints(frame, chunks[3]);
+            return;
+        }
+        if self.app.stats_panel == StatsPanel::Heatmap {
+            let chunks = Layout::vertical([
+                Constraint::Length(5),   // Stat cards row
+                Constraint::Length(11),  // Heatmap
+                Constraint::Min(4),      // Selected day's sessions
+                Constraint::Length(2),   // Hints
+            ])
+            .split(area);
+
+            self.render_stat_cards(frame, chunks[0]);
+            self.render_heatmap(frame, chunks[1]);
+            self.render_heatmap_day(frame, chunks[2]);
+            self.render_hints(frame, chunks[3]);
+            return;
+        }
+
         let chunks = Layout::vertical([
             Constraint::Length(5),  // Stat cards row
             Constraint::Length(6),  // Sparkline
@@ -34,20 +89,36 @@ impl<'a> StatsWidget<'a> {
 
         self.render_stat_cards(frame, chunks[0]);
         self.render_sparkline(frame, chunks[1]);
-        self.render_bar_chart(frame, chunks[2]);
+        let chart_chunks = Layout::horizontal([
+            Constraint::Percentage(55),
+            Constraint::Percentage(45),
+        ])
+        .split(chunks[2]);
+        self.render_bar_chart(frame, chart_chunks[0]);
+        self.render_focus_chart(frame, chart_chunks[1]);
         self.render_hints(frame, chunks[3]);
     }
 
     fn render_stat_cards(&self, frame: &mut Frame, area: Rect) {
-        let filtered: Vec<_> = self.app.filtered_sessions().into_iter().cloned().collect();
-        let stats = SessionStats::from_sessions(&filtered);
+        let stats = SessionStats::from_sessions(self.app.filtered_sessions()).with_streaks(self.app.streaks());
+        // Compare against the previous period, unless showing all time
+        let comparison = self.app.previous_sessions().map(|previous| {
+            StatsComparison::new(
+                SessionStats::from_sessions(self.app.filtered_sessions()),
+                SessionStats::from_sessions(previous),
+            )
+        });
+        let delta = |delta: fn(&StatsComparison) -> Delta, points: bool| {
+            comparison.as_ref().map(|comparison| delta_line(delta(comparison), points))
+        };
 
-        // Create 4 equal-width columns
+        // Create 5 equal-width columns
         let card_chunks = Layout::horizontal([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
         ])
         .split(area);
 
@@ -57,29 +128,47 @@ impl<'a> StatsWidget<'a> {
             card_chunks[0],
             &stats.total_sessions.to_string(),
             "Sessions",
+            delta(StatsComparison::sessions, false),
         );
 
         // Completion rate card
-        let completion_pct = if stats.total_sessions > 0 {
-            format!(
-                "{:.0}%",
-                stats.completed_sessions as f64 / stats.total_sessions as f64 * 100.0
-            )
-        } else {
-            "0%".to_string()
-        };
-        self.render_stat_card(frame, card_chunks[1], &completion_pct, "Complete");
+        let completion_pct = format!("{:.0}%", stats.completion_rate());
+        self.render_stat_card(
+            frame,
+            card_chunks[1],
+            &completion_pct,
+            "Complete",
+            delta(StatsComparison::completion_rate, true),
+        );
 
         // Focus time card
         let focus_time = format_duration_short(stats.total_focus_time_secs);
-        self.render_stat_card(frame, card_chunks[2], &focus_time, "Focus");
+        self.render_stat_card(
+            frame,
+            card_chunks[2],
+            &focus_time,
+            "Focus",
+            delta(StatsComparison::focus_time, false),
+        );
 
         // Average duration card
         let avg_duration = format_duration_short(stats.average_duration_secs);
-        self.render_stat_card(frame, card_chunks[3], &avg_duration, "Average");
+        self.render_stat_card(
+            frame,
+            card_chunks[3],
+            &avg_duration,
+            "Average",
+            delta(StatsComparison::average_duration, false),
+        );
+
+        // Goal streak card
+        if let Some(streaks) = stats.streaks {
+            let best = format!("Streak (best {})", streaks.longest);
+            self.render_stat_card(frame, card_chunks[4], &format_days(streaks.current), &best, None);
+        }
     }
 
-    fn render_stat_card(&self, frame: &mut Frame, area: Rect, value: &str, label: &str) {
+    fn render_stat_card(&self, frame: &mut Frame, area: Rect, value: &str, label: &str, delta: Option<Line>) {
         let inner_chunks = Layout::vertical([
             Constraint::Length(2), // Value
             Constraint::Length(1), // Label
@@ -89,12 +178,12 @@ impl<'a> StatsWidget<'a> {
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(ratatui::style::Style::default().fg(BORDER));
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border));
         frame.render_widget(block, area);
 
-        let value_widget = Paragraph::new(value)
-            .style(Theme::stat_value())
-            .alignment(Alignment::Center);
+        let mut lines = vec![Line::styled(value, Theme::stat_value())];
+        lines.extend(delta);
+        let value_widget = Paragraph::new(lines).alignment(Alignment::Center);
         frame.render_widget(value_widget, inner_chunks[0]);
 
         let label_widget = Paragraph::new(label)
@@ -110,11 +199,11 @@ impl<'a> StatsWidget<'a> {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(ratatui::style::Style::default().fg(BORDER))
+                    .border_style(ratatui::style::Style::default().fg(Theme::current().border))
                     .title(" 7-Day Trend ")
                     .title_style(
                         ratatui::style::Style::default()
-                            .fg(ACCENT)
+                            .fg(Theme::current().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
             )
@@ -125,79 +214,470 @@ impl<'a> StatsWidget<'a> {
     }
 
     fn render_bar_chart(&self, frame: &mut Frame, area: Rect) {
-        let weekly_data = self.app.weekly_bar_data();
+        let chart = self.app.focus_chart();
 
-        // Convert to minutes for better display
-        let bars: Vec<Bar> = weekly_data
+        let bars: Vec<Bar> = chart
+            .bars
             .iter()
-            .map(|(label, secs)| {
-                let minutes = *secs / 60;
+            .map(|bar| {
                 Bar::default()
-                    .value(minutes)
-                    .label(Line::from(*label))
-                    .style(ratatui::style::Style::default().fg(PRIMARY))
+                    .value(bar.value(chart.scale))
+                    .label(Line::from(bar.label.clone()))
+                    .style(ratatui::style::Style::default().fg(Theme::current().primary))
                     .value_style(
                         ratatui::style::Style::default()
-                            .fg(TEXT_BRIGHT)
+                            .fg(Theme::current().text_bright)
                             .add_modifier(Modifier::BOLD),
                     )
             })
             .collect();
 
+        // Fit every bar in the panel: twelve months need narrower bars than seven days
+        let gap = if bars.len() > 7 { 1 } else { 2 };
+        let bar_width = ((area.width.saturating_sub(2) + gap) / bars.len().max(1) as u16)
+            .saturating_sub(gap)
+            .clamp(1, 5);
+
         let bar_chart = BarChart::default()
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(ratatui::style::Style::default().fg(BORDER))
-                    .title(" Weekly Activity (minutes) ")
+                    .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+                    .title(format!(" {} ({}) ", chart.title, chart.scale.unit()))
                     .title_style(
                         ratatui::style::Style::default()
-                            .fg(ACCENT)
+                            .fg(Theme::current().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
             )
             .data(BarGroup::default().bars(&bars))
-            .bar_width(5)
-            .bar_gap(2)
-            .bar_style(ratatui::style::Style::default().fg(PRIMARY))
+            .bar_width(bar_width)
+            .bar_gap(gap)
+            .bar_style(Theme::bar_chart())
             .value_style(
                 ratatui::style::Style::default()
-                    .fg(TEXT_BRIGHT)
+                    .fg(Theme::current().text_bright)
                     .add_modifier(Modifier::BOLD),
             );
 
         frame.render_widget(bar_chart, area);
     }
 
-    fn render_hints(&self, frame: &mut Frame, area: Rect) {
-        let hints = vec![
-            ("Tab", "Switch View"),
-            ("f", "Filter"),
-            ("q", "Quit"),
-        ];
+    fn render_focus_chart(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" Focus by Hour (1-5) ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
 
-        let filter_info = Span::styled(
-            format!("Filter: {} ", self.app.filter_label()),
-            ratatui::style::Style::default().fg(SUCCESS),
-        );
+        let focus_data = self.app.focus_by_hour();
+        if focus_data.is_empty() {
+            let empty = Paragraph::new("Rate your focus after a pomodoro to see it here")
+                .style(Theme::muted())
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        // Bars are scaled by ten so the average keeps one decimal place
+        let bars: Vec<Bar> = focus_data
+            .iter()
+            .map(|(hour, focus)| {
+                Bar::default()
+                    .value((focus * 10.0).round() as u64)
+                    .text_value(format!("{:.1}", focus))
+                    .label(Line::from(format!("{:02}", hour)))
+                    .style(ratatui::style::Style::default().fg(Theme::current().success))
+            })
+            .collect();
+
+        let bar_chart = BarChart::default()
+            .block(block)
+            .data(BarGroup::default().bars(&bars))
+            .max(50)
+            .bar_width(3)
+            .bar_gap(1)
+            .value_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().text_bright)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_widget(bar_chart, area);
+    }
+
+    fn render_heatmap(&self, frame: &mut Frame, area: Rect) {
+        let heatmap = self.app.heatmap();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" Focus Heatmap (vs. daily goal) ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        // Show as many recent weeks as fit, scrolling left to keep the selection visible
+        let visible = ((inner.width as usize).saturating_sub(4) / 2).min(WEEKS);
+        let selected_week = heatmap.week_of(self.app.heatmap_selected);
+        let first = (WEEKS - visible).min(selected_week);
+
+        let mut header = vec![Span::raw("    ")];
+        let mut week = first;
+        while week < first + visible {
+            match heatmap.month_label(week) {
+                Some(label) if week + 1 < first + visible => {
+                    header.push(Span::styled(format!("{} ", label), Theme::muted()));
+                    week += 2;
+                }
+                _ => {
+                    header.push(Span::raw("  "));
+                    week += 1;
+                }
+            }
+        }
 
-        let hint_spans: Vec<Span> = std::iter::once(filter_info)
-            .chain(std::iter::once(Span::raw(" | ")))
-            .chain(hints.iter().enumerate().flat_map(|(i, (key, action))| {
-                let mut spans = vec![
-                    Span::styled(format!("[{}]", key), Theme::key_hint()),
-                    Span::raw(" "),
-                    Span::styled(*action, Theme::key_action()),
-                ];
-                if i < hints.len() - 1 {
+        let mut lines = vec![Line::from(header)];
+        for (weekday, name) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let mut spans = vec![Span::styled(format!("{:<4}", name), Theme::muted())];
+            for week in first..first + visible {
+                let Some(date) = heatmap.date_at(week, weekday) else {
                     spans.push(Span::raw("  "));
+                    continue;
+                };
+                let level = heatmap.level(date);
+                let mut style = Theme::heatmap(level);
+                if date == self.app.heatmap_selected {
+                    style = if Theme::current().monochrome {
+                        style.add_modifier(Modifier::REVERSED)
+                    } else {
+                        style.bg(Theme::current().highlight)
+                    };
                 }
-                spans
+                spans.push(Span::styled(Theme::heatmap_cell(level), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let mut legend = vec![Span::styled("    Less ", Theme::muted())];
+        for level in 0..=4 {
+            legend.push(Span::styled(format!("{} ", Theme::heatmap_cell(level)), Theme::heatmap(level)));
+        }
+        legend.push(Span::styled("More", Theme::muted()));
+        lines.push(Line::from(legend));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_heatmap_day(&self, frame: &mut Frame, area: Rect) {
+        let date = self.app.heatmap_selected;
+        let sessions = self.app.heatmap_sessions();
+        let stats = SessionStats::from_sessions(sessions.iter().copied());
+        let goal = self.app.heatmap().goal_secs(date).max(1);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border())
+            .title(format!(
+                " {} - {} focus ({:.0}% of goal) ",
+                date.format("%A %Y-%m-%d"),
+                format_duration_short(stats.total_focus_time_secs),
+                stats.total_focus_time_secs as f64 / goal as f64 * 100.0
+            ))
+            .title_style(Theme::subtitle());
+
+        if sessions.is_empty() {
+            let empty = Paragraph::new("No sessions on this day")
+                .style(Theme::muted())
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let task_width = (area.width as usize).saturating_sub(2 + 7 + 8 + 12);
+        let lines: Vec<Line> = sessions
+            .iter()
+            .map(|session| {
+                let status = if session.completed {
+                    Span::styled("Completed", Theme::status_completed())
+                } else {
+                    Span::styled("Interrupted", Theme::status_interrupted())
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}  ", session.started_at.with_timezone(&Local).format("%H:%M")),
+                        Theme::muted(),
+                    ),
+                    Span::styled(pad(&session.task, task_width), Theme::bright()),
+                    Span::raw(format!(" {:>6}  ", format_duration_short(session.duration_secs))),
+                    status,
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_hour_distribution(&self, frame: &mut Frame, area: Rect) {
+        let distribution = self.app.distribution();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" Focus by Hour of Day (minutes, colored by completion rate) ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        // Fit all 24 hours, leaving a one column gap between bars
+        let bar_width = ((block.inner(area).width + 1) / 24).saturating_sub(1).clamp(1, 3);
+        let bars: Vec<Bar> = distribution
+            .by_hour
+            .iter()
+            .enumerate()
+            .map(|(hour, bucket)| {
+                Bar::default()
+                    .value((bucket.focus_secs / 60) as u64)
+                    .label(Line::from(format!("{:02}", hour)))
+                    .style(completion_style(bucket))
+            })
+            .collect();
+
+        let bar_chart = BarChart::default()
+            .block(block)
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .value_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().text_bright)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_widget(bar_chart, area);
+    }
+
+    fn render_weekday_distribution(&self, frame: &mut Frame, area: Rect) {
+        let distribution = self.app.distribution();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" Focus by Weekday ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+
+        // Day name, bar, then focus time, session count and completion rate
+        let bar_width = (inner.width as usize).saturating_sub(5 + 9 + 13 + 12);
+        let max = distribution
+            .by_weekday
+            .iter()
+            .map(|bucket| bucket.focus_secs)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let lines: Vec<Line> = WEEKDAYS
+            .iter()
+            .zip(distribution.by_weekday)
+            .map(|(name, bucket)| {
+                let filled = (bucket.focus_secs as usize * bar_width).div_ceil(max as usize);
+                let completion = bucket
+                    .completion_rate()
+                    .map_or("-".to_string(), |rate| format!("{:.0}%", rate));
+                Line::from(vec![
+                    Span::styled(format!("{:<5}", name), Theme::stat_label()),
+                    Span::styled("█".repeat(filled), completion_style(&bucket)),
+                    Span::raw(" ".repeat(bar_width - filled)),
+                    Span::styled(
+                        format!("{:>9}", format_duration_short(bucket.focus_secs)),
+                        Theme::bright(),
+                    ),
+                    Span::styled(format!("{:>4} sessions", bucket.sessions), Theme::muted()),
+                    Span::styled(format!("{:>6} done", completion), Theme::muted()),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_estimate_tasks(&self, frame: &mut Frame, area: Rect) {
+        let report = self.app.estimate_report();
+        let title = match report.average_error() {
+            Some(average) => format!(
+                " Estimates - {} across {} ",
+                estimate::describe(average),
+                estimate::format_tasks(report.tasks.len() as u32)
+            ),
+            None => " Estimates ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::border())
+            .title(title)
+            .title_style(Theme::subtitle());
+
+        if report.tasks.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from("No finished tasks with an estimate in this period"),
+                Line::from("Estimate tasks in the Plan tab, or end the task prompt with ~N (e.g. \"Write report ~3\")"),
+            ])
+            .style(Theme::muted())
+            .alignment(Alignment::Center)
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let task_width = (area.width as usize).saturating_sub(2 + 8 + 24);
+        let header = Line::from(vec![
+            Span::raw("        "),
+            Span::raw(pad("Task", task_width)),
+            Span::raw(format!("{:>6}{:>8}{:>10}", "Est", "Actual", "Error")),
+        ])
+        .style(Theme::table_header());
+        let lines: Vec<Line> = std::iter::once(header)
+            .chain(report.tasks.iter().map(|task| {
+                Line::from(vec![
+                    Span::styled(format!("{}  ", task.finished_on.format("%b %d")), Theme::muted()),
+                    Span::styled(pad(&task.title, task_width), Theme::bright()),
+                    Span::raw(format!("{:>6}{:>8}", task.estimate, task.actual)),
+                    Span::styled(format!("{:>+9.0}%", task.error_percent()), accuracy_style(task.error_percent())),
+                ])
             }))
             .collect();
 
-        let hints_paragraph = Paragraph::new(Line::from(hint_spans)).alignment(Alignment::Center);
-        frame.render_widget(hints_paragraph, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_estimate_trend(&self, frame: &mut Frame, area: Rect) {
+        let weeks = self.app.estimate_report().by_week();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" Accuracy by Week (+ took longer than estimated) ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+
+        // Most recent weeks that fit, oldest at the top
+        let shown = &weeks[weeks.len().saturating_sub(inner.height as usize)..];
+        let bar_width = (inner.width as usize).saturating_sub(8 + 8 + 11);
+        let max = shown
+            .iter()
+            .map(|week| week.average_error.abs())
+            .fold(0.0, f64::max)
+            .max(1.0);
+        let lines: Vec<Line> = shown
+            .iter()
+            .map(|week| {
+                let filled = ((week.average_error.abs() / max) * bar_width as f64).round() as usize;
+                Line::from(vec![
+                    Span::styled(format!("{}  ", week.week.format("%b %d")), Theme::stat_label()),
+                    Span::styled("█".repeat(filled), accuracy_style(week.average_error)),
+                    Span::raw(" ".repeat(bar_width - filled)),
+                    Span::styled(format!("{:>+7.0}%", week.average_error), Theme::bright()),
+                    Span::styled(format!("{:>10}", estimate::format_tasks(week.tasks)), Theme::muted()),
+                ])
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let entries: &[(&[Action], &str)] = match self.app.stats_panel {
+            StatsPanel::Heatmap => &[
+                (&[Action::ScrollUp, Action::ScrollDown, Action::CursorLeft, Action::CursorRight], "Select Day"),
+                (&[Action::Heatmap, Action::Cancel], "Close Heatmap"),
+                (&[Action::NextTab], "Switch View"),
+                (&[Action::Quit], "Quit"),
+            ],
+            StatsPanel::Distribution => &[
+                (&[Action::CycleFilter], "Filter"),
+                (&[Action::Distribution, Action::Cancel], "Close Distribution"),
+                (&[Action::NextTab], "Switch View"),
+                (&[Action::Quit], "Quit"),
+            ],
+            StatsPanel::Estimates => &[
+                (&[Action::CycleFilter], "Filter"),
+                (&[Action::Estimates, Action::Cancel], "Close Estimates"),
+                (&[Action::NextTab], "Switch View"),
+                (&[Action::Quit], "Quit"),
+            ],
+            StatsPanel::Overview => &[
+                (&[Action::NextTab], "Switch View"),
+                (&[Action::CycleFilter], "Filter"),
+                (&[Action::PageUp, Action::PageDown], "Earlier/Later"),
+                (&[Action::Heatmap], "Heatmap"),
+                (&[Action::Distribution], "Distribution"),
+                (&[Action::Estimates], "Estimates"),
+                (&[Action::ShowHelp], "Help"),
+                (&[Action::Quit], "Quit"),
+            ],
+        };
+        let hints = self.app.keymap.hints(Context::Stats, entries);
+
+        let filter_info = Span::styled(
+            format!("Filter: {} ", self.app.filter_label()),
+            ratatui::style::Style::default().fg(Theme::current().success),
+        );
+
+        render_hints(frame, area, self.app, vec![filter_info, Span::raw(" | ")], &hints);
+    }
+}
+
+/// Arrow and change against the previous period, in percentage points for rates
+fn delta_line(delta: Delta, points: bool) -> Line<'static> {
+    let style = if delta.change > 0.0 {
+        Theme::success()
+    } else if delta.change < 0.0 {
+        Theme::warning()
+    } else {
+        Theme::muted()
+    };
+    let change = if points {
+        format!("{:+.0} pts", delta.change)
+    } else {
+        delta.percent_label()
+    };
+    Line::styled(format!("{} {}", delta.arrow(), change), style)
+}
+
+/// Estimation error within a quarter of the estimate counts as accurate
+fn accuracy_style(error_percent: f64) -> ratatui::style::Style {
+    if error_percent.abs() <= 25.0 {
+        Theme::success()
+    } else {
+        Theme::warning()
+    }
+}
+
+/// Bar color for a bucket's completion rate
+fn completion_style(bucket: &Bucket) -> ratatui::style::Style {
+    match bucket.completion_rate() {
+        Some(rate) if rate >= 80.0 => Theme::status_completed(),
+        Some(rate) if rate >= 50.0 => Theme::subtitle(),
+        Some(_) => Theme::status_interrupted(),
+        None => Theme::muted(),
     }
 }
 
@@ -8,20 +8,27 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::View;
-use crate::theme::{Theme, BORDER, PRIMARY, TEXT_MUTED};
+use crate::action::Action;
+use crate::app::{App, View};
+use crate::theme::Theme;
 
 /// Tab bar titles
-const TAB_TITLES: [&str; 3] = ["Timer", "History", "Stats"];
+const TAB_TITLES: [&str; 4] = ["Timer", "Plan", "History", "Stats"];
+
+/// Views in tab order
+const TAB_VIEWS: [View; 4] = [View::Timer, View::Plan, View::History, View::Stats];
+
+/// Separator between tab titles
+const DIVIDER: &str = " | ";
 
 /// Widget for displaying the tab bar
-pub struct TabsWidget {
-    current: View,
+pub struct TabsWidget<'a> {
+    app: &'a App,
 }
 
-impl TabsWidget {
-    pub fn new(current: View) -> Self {
-        Self { current }
+impl<'a> TabsWidget<'a> {
+    pub fn new(app: &'a App) -> Self {
+        Self { app }
     }
 
     /// Render the tabs widget
@@ -30,7 +37,7 @@ impl TabsWidget {
             .iter()
             .enumerate()
             .map(|(i, t)| {
-                if i == self.current.index() {
+                if i == self.app.current_view.index() {
                     Line::styled(*t, Theme::tab_active())
                 } else {
                     Line::styled(*t, Theme::tab_inactive())
@@ -38,23 +45,36 @@ impl TabsWidget {
             })
             .collect();
 
+        let block = Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
+            .title(" TOMATOCRAB ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().primary)
+                    .add_modifier(Modifier::BOLD),
+            );
+        self.add_click_targets(block.inner(area));
+
         let tabs = Tabs::new(titles)
-            .block(
-                Block::default()
-                    .borders(Borders::BOTTOM)
-                    .border_style(ratatui::style::Style::default().fg(BORDER))
-                    .title(" TOMATOCRAB ")
-                    .title_style(
-                        ratatui::style::Style::default()
-                            .fg(PRIMARY)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-            )
-            .select(self.current.index())
-            .style(ratatui::style::Style::default().fg(TEXT_MUTED))
+            .block(block)
+            .select(self.app.current_view.index())
+            .style(ratatui::style::Style::default().fg(Theme::current().text_muted))
             .highlight_style(Theme::tab_active())
-            .divider(" | ");
+            .divider(DIVIDER);
 
         frame.render_widget(tabs, area);
     }
+
+    /// Make each title, with the padding around it, switch to its view when clicked
+    fn add_click_targets(&self, area: Rect) {
+        let mut mouse = self.app.mouse.borrow_mut();
+        let mut x = area.x;
+        for (title, view) in TAB_TITLES.iter().zip(TAB_VIEWS) {
+            let width = title.len() as u16 + 2;
+            let tab = Rect::new(x, area.y, width, 1);
+            mouse.click(tab.intersection(area), Action::ShowView(view));
+            x += width + DIVIDER.len() as u16;
+        }
+    }
 }
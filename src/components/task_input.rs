@@ -3,13 +3,15 @@
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::Modifier,
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    text::Line,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use crate::app::App;
-use crate::theme::{Theme, ACCENT, BORDER, HIGHLIGHT, PRIMARY, TEXT_BRIGHT};
+use crate::action::Action;
+use crate::app::{App, Suggestion};
+use crate::components::hints::{render_hints, Hint};
+use crate::theme::Theme;
 
 /// Widget for entering task description
 pub struct TaskInputWidget<'a> {
@@ -26,11 +28,11 @@ impl<'a> TaskInputWidget<'a> {
         // Main container with border
         let outer_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(ratatui::style::Style::default().fg(BORDER))
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
             .title(" TOMATOCRAB ")
             .title_style(
                 ratatui::style::Style::default()
-                    .fg(PRIMARY)
+                    .fg(Theme::current().primary)
                     .add_modifier(Modifier::BOLD),
             )
             .title_alignment(Alignment::Center);
@@ -43,20 +45,34 @@ impl<'a> TaskInputWidget<'a> {
             Constraint::Length(2),  // Prompt
             Constraint::Length(1),  // Spacer
             Constraint::Length(3),  // Input field
-            Constraint::Min(2),     // Spacer
+            Constraint::Min(2),     // Suggestions and Taskwarrior picker / spacer
             Constraint::Length(2),  // Hints
         ])
         .split(inner_area);
 
         self.render_prompt(frame, chunks[1]);
         self.render_input(frame, chunks[3]);
+        let suggestions = self.app.task_suggestions();
+        let listed = if suggestions.is_empty() { 0 } else { suggestions.len() as u16 + 2 };
+        let [suggestions_area, picker_area] =
+            Layout::vertical([Constraint::Length(listed), Constraint::Min(0)]).areas(chunks[4]);
+        self.render_suggestions(frame, suggestions_area, &suggestions);
+        if self.app.integrations.taskwarrior.is_some() {
+            self.render_taskwarrior_picker(frame, picker_area);
+        }
         self.render_hints(frame, chunks[5]);
     }
 
     fn render_prompt(&self, frame: &mut Frame, area: Rect) {
-        let prompt = Paragraph::new("What are you working on?")
-            .style(Theme::subtitle())
-            .alignment(Alignment::Center);
+        let mut lines = vec![Line::styled("What are you working on?", Theme::subtitle())];
+        if self.app.task_suggested {
+            lines.push(Line::styled(
+                "Next up from todo.txt - Enter to start, or type to replace",
+                Theme::muted(),
+            ));
+        }
+
+        let prompt = Paragraph::new(lines).alignment(Alignment::Center);
         frame.render_widget(prompt, area);
     }
 
@@ -72,45 +88,114 @@ impl<'a> TaskInputWidget<'a> {
             height: area.height,
         };
 
-        // Blinking cursor effect
-        let input_text = format!("{}|", self.app.task_description);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(ratatui::style::Style::default().fg(Theme::current().highlight))
+            .title(" Task Description ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(Theme::current().accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(centered_area);
+
+        // Scroll long text so the cursor stays in view
+        let (visible, cursor) = self.app.task_input.view(inner.width as usize);
+        let input = Paragraph::new(visible)
+            .style(ratatui::style::Style::default().fg(Theme::current().text_bright))
+            .block(block);
+        frame.render_widget(input, centered_area);
+        frame.set_cursor_position((inner.x + cursor as u16, inner.y));
+    }
+
+    fn render_suggestions(&self, frame: &mut Frame, area: Rect, suggestions: &[Suggestion]) {
+        if suggestions.is_empty() {
+            return;
+        }
+
+        let list_width = area.width.min(60);
+        let horizontal_padding = (area.width.saturating_sub(list_width)) / 2;
 
-        let input = Paragraph::new(input_text)
-            .style(ratatui::style::Style::default().fg(TEXT_BRIGHT))
+        let centered_area = Rect {
+            x: area.x + horizontal_padding,
+            y: area.y,
+            width: list_width,
+            height: area.height.min(suggestions.len() as u16 + 2),
+        };
+
+        let items: Vec<ListItem> = suggestions
+            .iter()
+            .map(|suggestion| ListItem::new(suggestion.text.clone()).style(Theme::table_row()))
+            .collect();
+
+        let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(ratatui::style::Style::default().fg(HIGHLIGHT))
-                    .title(" Task Description ")
+                    .border_style(Theme::border())
+                    .title(" Suggestions ")
                     .title_style(
                         ratatui::style::Style::default()
-                            .fg(ACCENT)
+                            .fg(Theme::current().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
-            );
-        frame.render_widget(input, centered_area);
+            )
+            .highlight_style(Theme::table_row_selected());
+
+        let mut state = ListState::default();
+        state.select(self.app.completion.as_ref().map(|completion| completion.selected));
+        frame.render_stateful_widget(list, centered_area, &mut state);
     }
 
-    fn render_hints(&self, frame: &mut Frame, area: Rect) {
-        let hints = vec![("Enter", "Start Timer"), ("Esc", "Cancel")];
+    fn render_taskwarrior_picker(&self, frame: &mut Frame, area: Rect) {
+        let picker_width = area.width.min(60);
+        let horizontal_padding = (area.width.saturating_sub(picker_width)) / 2;
+
+        let centered_area = Rect {
+            x: area.x + horizontal_padding,
+            y: area.y,
+            width: picker_width,
+            height: area.height.min(12),
+        };
 
-        let hint_spans: Vec<Span> = hints
+        let items: Vec<ListItem> = self
+            .app
+            .matching_taskwarrior_tasks()
             .iter()
-            .enumerate()
-            .flat_map(|(i, (key, action))| {
-                let mut spans = vec![
-                    Span::styled(format!("[{}]", key), Theme::key_hint()),
-                    Span::raw(" "),
-                    Span::styled(*action, Theme::key_action()),
-                ];
-                if i < hints.len() - 1 {
-                    spans.push(Span::raw("  "));
-                }
-                spans
-            })
+            .map(|task| ListItem::new(task.label()).style(Theme::table_row()))
             .collect();
 
-        let hints_paragraph = Paragraph::new(Line::from(hint_spans)).alignment(Alignment::Center);
-        frame.render_widget(hints_paragraph, area);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Theme::border())
+                    .title(" Taskwarrior ")
+                    .title_style(
+                        ratatui::style::Style::default()
+                            .fg(Theme::current().accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .highlight_style(Theme::table_row_selected());
+
+        let mut state = ListState::default();
+        state.select(self.app.taskwarrior_selected);
+        frame.render_stateful_widget(list, centered_area, &mut state);
+    }
+
+    fn render_hints(&self, frame: &mut Frame, area: Rect) {
+        let hints = [
+            Hint::new("Enter", "Start Timer", Action::Confirm),
+            Hint::new("Tab", "Complete", Action::NextTab),
+            if self.app.integrations.taskwarrior.is_some() {
+                Hint::new("Up/Down", "Pick Task", Action::ScrollDown)
+            } else {
+                Hint::new("Up/Down", "History", Action::ScrollUp)
+            },
+            Hint::new("Esc", "Cancel", Action::Cancel),
+        ];
+
+        render_hints(frame, area, self.app, Vec::new(), &hints);
     }
 }
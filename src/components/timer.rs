@@ -3,16 +3,19 @@
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::Modifier,
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Gauge, Paragraph},
     Frame,
 };
 
+use crate::action::Action;
 use crate::app::{App, AppState, TimerMode};
-use crate::theme::{
-    Theme, BORDER, PRIMARY, SUCCESS, TIMER_BREAK, TIMER_FINISHED, TIMER_IDLE, TIMER_LONG_BREAK,
-    TIMER_PAUSED, TIMER_RUNNING,
-};
+use crate::components::hints::render_hints;
+use crate::components::session_list::format_days;
+use crate::goal::GoalTarget;
+use crate::keymap::Context;
+use crate::text::truncate;
+use crate::theme::Theme;
 
 /// Widget for displaying the timer
 pub struct TimerWidget<'a> {
@@ -29,11 +32,11 @@ impl<'a> TimerWidget<'a> {
         // Main container with border
         let outer_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(ratatui::style::Style::default().fg(BORDER))
+            .border_style(ratatui::style::Style::default().fg(Theme::current().border))
             .title(" TOMATOCRAB ")
             .title_style(
                 ratatui::style::Style::default()
-                    .fg(PRIMARY)
+                    .fg(Theme::current().primary)
                     .add_modifier(Modifier::BOLD),
             )
             .title_alignment(Alignment::Center);
@@ -48,6 +51,7 @@ impl<'a> TimerWidget<'a> {
             Constraint::Length(1),  // Spacer
             Constraint::Length(3),  // Progress bar with labels
             Constraint::Length(2),  // Status
+            Constraint::Length(1),  // Daily goal
             Constraint::Min(0),     // Flexible spacer
             Constraint::Length(2),  // Keyboard hints
         ])
@@ -57,7 +61,8 @@ impl<'a> TimerWidget<'a> {
         self.render_big_timer(frame, chunks[2]);
         self.render_progress(frame, chunks[4]);
         self.render_status(frame, chunks[5]);
-        self.render_hints(frame, chunks[7]);
+        self.render_goal(frame, chunks[6]);
+        self.render_hints(frame, chunks[8]);
     }
 
     fn render_task(&self, frame: &mut Frame, area: Rect) {
@@ -71,14 +76,27 @@ impl<'a> TimerWidget<'a> {
             }
             (_, TimerMode::ShortBreak) => "Take a short break - stretch, hydrate!".to_string(),
             (_, TimerMode::LongBreak) => "Long break - you've earned it! Rest well.".to_string(),
-            (AppState::WorkFinished, _) => {
-                format!("Completed: {}", self.app.task_description)
-            }
+            (AppState::WorkFinished, _) => match &self.app.linked_todo {
+                Some(item) => format!(
+                    "Completed: {} ({} pomodoros)",
+                    self.app.task_description,
+                    item.progress()
+                ),
+                None => format!("Completed: {}", self.app.task_description),
+            },
             (AppState::BreakFinished, _) => "Break complete - ready for another session?".to_string(),
-            _ => format!("Working on: {}", self.app.task_description),
+            _ => match &self.app.linked_todo {
+                Some(item) => format!(
+                    "Working on: {} (pomodoro {} of {})",
+                    self.app.task_description,
+                    item.actual + 1,
+                    item.estimate.map_or("?".to_string(), |estimate| estimate.to_string())
+                ),
+                None => format!("Working on: {}", self.app.task_description),
+            },
         };
 
-        let task = Paragraph::new(task_text)
+        let task = Paragraph::new(truncate(&task_text, area.width as usize))
             .style(Theme::subtitle())
             .alignment(Alignment::Center);
         frame.render_widget(task, area);
@@ -89,17 +107,29 @@ impl<'a> TimerWidget<'a> {
         let seconds = self.app.remaining_secs % 60;
 
         let color = match (&self.app.state, &self.app.timer_mode) {
-            (AppState::Running, TimerMode::ShortBreak) => TIMER_BREAK,
-            (AppState::Running, TimerMode::LongBreak) => TIMER_LONG_BREAK,
-            (AppState::Running, TimerMode::Work) => TIMER_RUNNING,
-            (AppState::Paused, _) => TIMER_PAUSED,
-            (AppState::WorkFinished, _) => TIMER_FINISHED,
-            (AppState::BreakFinished, _) => TIMER_BREAK,
-            _ => TIMER_IDLE,
+            (AppState::Running, TimerMode::ShortBreak) => Theme::current().timer_break,
+            (AppState::Running, TimerMode::LongBreak) => Theme::current().timer_long_break,
+            (AppState::Running, TimerMode::Work) => Theme::current().timer_running,
+            (AppState::Paused, _) => Theme::current().timer_paused,
+            (AppState::WorkFinished, _) => Theme::current().timer_finished,
+            (AppState::BreakFinished, _) => Theme::current().timer_break,
+            _ => Theme::current().timer_idle,
         };
 
         // Create big ASCII art digits
-        let big_text = create_big_time(minutes, seconds);
+        let big_text = Text::from(create_big_time(minutes, seconds));
+
+        // Clicking the digits pauses and resumes a work session
+        if self.app.timer_actions().contains(&Action::TogglePause) {
+            let width = big_text.width() as u16;
+            let digits = Rect::new(
+                area.x + (area.width / 2).saturating_sub(width / 2),
+                area.y,
+                width,
+                big_text.height() as u16,
+            );
+            self.app.mouse.borrow_mut().click(digits.intersection(area), Action::TogglePause);
+        }
 
         let timer = Paragraph::new(big_text)
             .style(ratatui::style::Style::default().fg(color).add_modifier(Modifier::BOLD))
@@ -146,102 +176,133 @@ impl<'a> TimerWidget<'a> {
         frame.render_widget(remaining_widget, progress_chunks[2]);
     }
 
+    fn render_goal(&self, frame: &mut Frame, area: Rect) {
+        let (achieved, target) = self.app.goal_progress();
+        let streak = self.app.streaks().current;
+
+        let mut spans = if target.amount() == 0 {
+            vec![Span::styled("Rest day", Theme::muted())]
+        } else {
+            let label = format!("{}/{} {}", achieved, target.amount(), target.unit());
+            let style = if achieved >= target.amount() { Theme::success() } else { Theme::bright() };
+            let mut spans = vec![Span::styled("Today's goal ", Theme::muted())];
+            // One box per pomodoro; minute goals get a ten-box bar
+            let boxes = match target {
+                GoalTarget::Pomodoros(amount) => amount.min(12),
+                GoalTarget::Minutes(_) => 10,
+            };
+            let filled = (achieved as u64 * boxes as u64 / target.amount() as u64).min(boxes as u64) as usize;
+            spans.push(Span::styled("■".repeat(filled), Theme::success()));
+            spans.push(Span::styled("□".repeat(boxes as usize - filled), Theme::muted()));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(label, style));
+            spans
+        };
+        if streak > 0 {
+            spans.push(Span::styled(format!("  ·  Streak: {}", format_days(streak)), Theme::subtitle()));
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), area);
+    }
+
     fn render_status(&self, frame: &mut Frame, area: Rect) {
         let (status_text, style) = match (&self.app.state, &self.app.timer_mode) {
             (AppState::Idle, _) => ("READY", Theme::muted()),
             (AppState::EnteringTask, _) => ("ENTER TASK", Theme::subtitle()),
             (AppState::Running, TimerMode::Work) => (
                 "FOCUS TIME",
-                ratatui::style::Style::default().fg(SUCCESS).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().success).add_modifier(Modifier::BOLD),
             ),
             (AppState::Running, TimerMode::ShortBreak) => (
                 "SHORT BREAK",
-                ratatui::style::Style::default().fg(TIMER_BREAK).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().timer_break).add_modifier(Modifier::BOLD),
             ),
             (AppState::Running, TimerMode::LongBreak) => (
                 "LONG BREAK",
-                ratatui::style::Style::default().fg(TIMER_LONG_BREAK).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().timer_long_break).add_modifier(Modifier::BOLD),
             ),
             (AppState::Paused, _) => (
                 "PAUSED",
-                ratatui::style::Style::default().fg(TIMER_PAUSED).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().timer_paused).add_modifier(Modifier::BOLD),
             ),
             (AppState::WorkFinished, _) => (
                 "SESSION COMPLETE!",
-                ratatui::style::Style::default().fg(TIMER_FINISHED).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().timer_finished).add_modifier(Modifier::BOLD),
             ),
             (AppState::BreakFinished, _) => (
                 "BREAK OVER",
-                ratatui::style::Style::default().fg(TIMER_BREAK).add_modifier(Modifier::BOLD),
+                ratatui::style::Style::default().fg(Theme::current().timer_break).add_modifier(Modifier::BOLD),
             ),
         };
 
-        let status = Paragraph::new(status_text)
-            .style(style)
-            .alignment(Alignment::Center);
+        let mut lines = vec![Line::styled(status_text, style)];
+        if let Some(notice) = &self.app.notice {
+            lines.push(Line::styled(notice.as_str(), Theme::warning()));
+        }
+
+        let status = Paragraph::new(lines).alignment(Alignment::Center);
         frame.render_widget(status, area);
     }
 
     fn render_hints(&self, frame: &mut Frame, area: Rect) {
-        let hints = match (&self.app.state, &self.app.timer_mode) {
+        let mut entries: Vec<(&[Action], &str)> = match (&self.app.state, &self.app.timer_mode) {
             (AppState::Idle, _) => vec![
-                ("Enter", "Start"),
-                ("Tab", "View"),
-                ("q", "Quit"),
+                (&[Action::Confirm], "Start"),
+                (&[Action::NextTab], "View"),
+                (&[Action::Quit], "Quit"),
             ],
             (AppState::EnteringTask, _) => vec![
-                ("Enter", "Confirm"),
-                ("Esc", "Cancel"),
+                (&[Action::Confirm], "Confirm"),
+                (&[Action::Cancel], "Cancel"),
             ],
             (AppState::Running, TimerMode::Work) => vec![
-                ("Space", "Pause"),
-                ("r", "Stop"),
-                ("Tab", "View"),
-                ("q", "Quit"),
+                (&[Action::TogglePause], "Pause"),
+                (&[Action::Stop], "Stop"),
+                (&[Action::NextTab], "View"),
+                (&[Action::Quit], "Quit"),
             ],
             (AppState::Running, TimerMode::ShortBreak | TimerMode::LongBreak) => vec![
-                ("s", "Skip"),
-                ("r", "Stop"),
-                ("Tab", "View"),
-                ("q", "Quit"),
+                (&[Action::Skip], "Skip"),
+                (&[Action::Stop], "Stop"),
+                (&[Action::NextTab], "View"),
+                (&[Action::Quit], "Quit"),
             ],
             (AppState::Paused, _) => vec![
-                ("Space", "Resume"),
-                ("r", "Stop"),
-                ("Tab", "View"),
-                ("q", "Quit"),
+                (&[Action::TogglePause], "Resume"),
+                (&[Action::Stop], "Stop"),
+                (&[Action::NextTab], "View"),
+                (&[Action::Quit], "Quit"),
             ],
             (AppState::WorkFinished, _) => vec![
-                ("b", "Break"),
-                ("Enter", "New Task"),
-                ("s", "Skip"),
-                ("q", "Quit"),
+                (&[Action::StartBreak], "Break"),
+                (&[Action::Confirm], "New Task"),
+                (&[Action::Repeat], "Repeat"),
+                (&[Action::Notes], "Notes"),
+                (&[Action::Skip], "Skip"),
+                (&[Action::Quit], "Quit"),
             ],
             (AppState::BreakFinished, _) => vec![
-                ("Enter", "New Task"),
-                ("s", "Idle"),
-                ("q", "Quit"),
+                (&[Action::Confirm], "New Task"),
+                (&[Action::Repeat], "Repeat"),
+                (&[Action::Notes], "Notes"),
+                (&[Action::Skip], "Idle"),
+                (&[Action::Quit], "Quit"),
             ],
         };
+        if self.app.state == AppState::Idle && self.app.last_task().is_some() {
+            entries.insert(1, (&[Action::Repeat], "Repeat Last"));
+        }
+        if matches!(self.app.state, AppState::WorkFinished | AppState::BreakFinished)
+            && self.app.can_mark_done()
+        {
+            entries.insert(entries.len() - 1, (&[Action::Done], "Done"));
+        }
+        if self.app.state != AppState::EnteringTask {
+            entries.insert(entries.len() - 1, (&[Action::ShowHelp], "Help"));
+        }
+        let hints = self.app.keymap.hints(Context::Timer, &entries);
 
-        let hint_spans: Vec<Span> = hints
-            .iter()
-            .enumerate()
-            .flat_map(|(i, (key, action))| {
-                let mut spans = vec![
-                    Span::styled(format!("[{}]", key), Theme::key_hint()),
-                    Span::raw(" "),
-                    Span::styled(*action, Theme::key_action()),
-                ];
-                if i < hints.len() - 1 {
-                    spans.push(Span::raw("  "));
-                }
-                spans
-            })
-            .collect();
-
-        let hints_paragraph = Paragraph::new(Line::from(hint_spans)).alignment(Alignment::Center);
-        frame.render_widget(hints_paragraph, area);
+        render_hints(frame, area, self.app, Vec::new(), &hints);
     }
 }
 
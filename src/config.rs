@@ -0,0 +1,65 @@
+//! User configuration read from `config.toml` in the config directory
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use color_eyre::eyre::{Context, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+
+use crate::goal::DailyGoal;
+
+/// Settings loaded from the config file, all optional
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Daily focus goal
+    pub goal: DailyGoal,
+    /// Color theme: a preset name, the name of a file in the themes directory, or a path
+    pub theme: Option<String>,
+    /// Named sets of timer lengths, chosen with `--profile` or `:profile` in the timer
+    pub profiles: BTreeMap<String, Profile>,
+    /// Capture the mouse in the timer; on unless set to false
+    pub mouse: Option<bool>,
+}
+
+/// Timer lengths that replace the command-line values when the profile is chosen
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Minutes of work per pomodoro
+    pub duration: Option<u32>,
+    pub short_break: Option<u32>,
+    pub long_break: Option<u32>,
+    /// Work sessions before a long break
+    pub long_break_interval: Option<u32>,
+}
+
+impl Config {
+    /// Location of the config file
+    pub fn path() -> Option<PathBuf> {
+        ProjectDirs::from("com", "tomatocrab", "tomatocrab").map(|dirs| dirs.config_dir().join("config.toml"))
+    }
+
+    /// Directory of user theme files, selected by file name without `.toml`
+    pub fn themes_dir() -> Option<PathBuf> {
+        ProjectDirs::from("com", "tomatocrab", "tomatocrab").map(|dirs| dirs.config_dir().join("themes"))
+    }
+
+    /// Location of the key bindings file
+    pub fn keys_path() -> Option<PathBuf> {
+        ProjectDirs::from("com", "tomatocrab", "tomatocrab").map(|dirs| dirs.config_dir().join("keys.toml"))
+    }
+
+    /// Load the config file, using defaults if it does not exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+
+        let contents = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&contents).wrap_err_with(|| format!("Invalid config file {}", path.display()))
+    }
+}
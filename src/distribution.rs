@@ -0,0 +1,181 @@
+//! Focus time and completion rate by hour of day and by weekday
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
+
+use crate::report::format_hours_minutes;
+use crate::session::Session;
+
+/// Weekday names from Monday, as used in the distribution tables
+pub const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Width of the longest bar in the text tables
+const BAR_WIDTH: usize = 30;
+
+/// Totals for one hour of the day or one weekday
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bucket {
+    /// Focus time falling inside the bucket
+    pub focus_secs: u32,
+    /// Sessions that started in the bucket
+    pub sessions: u32,
+    /// Of those, the ones that ran their full duration
+    pub completed: u32,
+}
+
+impl Bucket {
+    /// Percentage of sessions started in this bucket that completed
+    pub fn completion_rate(&self) -> Option<f64> {
+        (self.sessions > 0).then(|| self.completed as f64 / self.sessions as f64 * 100.0)
+    }
+}
+
+/// When focus happens, bucketed by local hour of day and weekday
+#[derive(Debug, Clone, Default)]
+pub struct Distribution {
+    pub by_hour: [Bucket; 24],
+    /// Indexed from Monday
+    pub by_weekday: [Bucket; 7],
+}
+
+impl Distribution {
+    /// Bucket the sessions, splitting focus time that crosses an hour boundary
+    pub fn from_sessions<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> Self {
+        let mut distribution = Self::default();
+
+        for session in sessions {
+            let start = session.started_at.with_timezone(&Local);
+            for bucket in [
+                &mut distribution.by_hour[start.hour() as usize],
+                &mut distribution.by_weekday[start.weekday().num_days_from_monday() as usize],
+            ] {
+                bucket.sessions += 1;
+                if session.completed {
+                    bucket.completed += 1;
+                }
+            }
+
+            for (from, to) in session.focus_intervals() {
+                distribution.add_focus(from, to);
+            }
+        }
+        distribution
+    }
+
+    /// Spread the span from `from` to `to` over the hours it covers
+    fn add_focus(&mut self, from: DateTime<Utc>, to: DateTime<Utc>) {
+        // Whole seconds, so hour boundaries land exactly on the hour
+        let from = from.with_timezone(&Local);
+        let mut cursor = from.with_nanosecond(0).unwrap_or(from);
+        let end = to.with_timezone(&Local);
+        while cursor < end {
+            let into_hour = Duration::seconds((cursor.minute() * 60 + cursor.second()) as i64);
+            let next_hour = cursor - into_hour + Duration::hours(1);
+            let until = next_hour.min(end);
+            let secs = (until - cursor).num_seconds().max(0) as u32;
+
+            self.by_hour[cursor.hour() as usize].focus_secs += secs;
+            self.by_weekday[cursor.weekday().num_days_from_monday() as usize].focus_secs += secs;
+            cursor = until;
+        }
+    }
+
+    /// Whether there is anything to show
+    pub fn is_empty(&self) -> bool {
+        self.by_weekday.iter().all(|bucket| bucket.sessions == 0 && bucket.focus_secs == 0)
+    }
+
+    /// Render hour-of-day and weekday tables as text
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "By Hour of Day");
+        let hours: Vec<(String, Bucket)> = self
+            .by_hour
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.sessions > 0 || bucket.focus_secs > 0)
+            .map(|(hour, bucket)| (format!("{:02}:00", hour), *bucket))
+            .collect();
+        write_table(&mut out, "Hour", &hours);
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "By Weekday");
+        let weekdays: Vec<(String, Bucket)> = WEEKDAYS
+            .iter()
+            .zip(self.by_weekday)
+            .map(|(name, bucket)| (name.to_string(), bucket))
+            .collect();
+        write_table(&mut out, "Day", &weekdays);
+        out
+    }
+}
+
+/// Write one table of buckets with a bar scaled to the busiest row
+fn write_table(out: &mut String, heading: &str, rows: &[(String, Bucket)]) {
+    let max = rows.iter().map(|(_, bucket)| bucket.focus_secs).max().unwrap_or(0).max(1);
+
+    let _ = writeln!(
+        out,
+        "{:<6} {:<width$} {:>8} {:>9} {:>11}",
+        heading,
+        "",
+        "Focus",
+        "Sessions",
+        "Completion",
+        width = BAR_WIDTH
+    );
+    let _ = writeln!(out, "{}", "-".repeat(6 + BAR_WIDTH + 8 + 9 + 11 + 4));
+    for (label, bucket) in rows {
+        let bar = "█".repeat((bucket.focus_secs as usize * BAR_WIDTH).div_ceil(max as usize));
+        let completion = bucket
+            .completion_rate()
+            .map_or("-".to_string(), |rate| format!("{:.0}%", rate));
+        let _ = writeln!(
+            out,
+            "{:<6} {:<width$} {:>8} {:>9} {:>11}",
+            label,
+            bar,
+            format_hours_minutes(bucket.focus_secs),
+            bucket.sessions,
+            completion,
+            width = BAR_WIDTH
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::session::Pause;
+
+    fn local(hour: u32, min: u32) -> DateTime<Utc> {
+        Local.with_ymd_and_hms(2024, 5, 1, hour, min, 0).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn splits_focus_across_hours_and_skips_pauses() {
+        // 14:50 to 15:25 with a five minute pause from 15:05, started half a second past the minute
+        let mut session = Session::new(
+            "Write".to_string(),
+            local(14, 50) + Duration::milliseconds(500),
+            30 * 60,
+            true,
+        );
+        session.pauses.push(Pause {
+            started_at: local(15, 5) + Duration::milliseconds(500),
+            duration_secs: 5 * 60,
+        });
+
+        let distribution = Distribution::from_sessions([&session]);
+        assert_eq!(distribution.by_hour[14].focus_secs, 10 * 60);
+        assert_eq!(distribution.by_hour[15].focus_secs, 20 * 60);
+        assert_eq!(distribution.by_hour[14].sessions, 1);
+        assert_eq!(distribution.by_hour[15].sessions, 0);
+        let weekday = local(14, 50).with_timezone(&Local).weekday().num_days_from_monday() as usize;
+        assert_eq!(distribution.by_weekday[weekday].focus_secs, 30 * 60);
+    }
+}
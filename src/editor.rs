@@ -0,0 +1,134 @@
+//! Single-line text editor used for task entry
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::action::Action;
+
+/// An editable line of text with a cursor that moves by grapheme cluster
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEditor {
+    text: String,
+    /// Byte offset of the cursor, always on a grapheme boundary
+    cursor: usize,
+}
+
+impl LineEditor {
+    /// Create an editor holding `text`, with the cursor at the end
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let cursor = text.len();
+        Self { text, cursor }
+    }
+
+    /// The current text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text, moving the cursor to the end
+    pub fn set(&mut self, text: impl Into<String>) {
+        *self = Self::new(text);
+    }
+
+    /// Remove all text
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Take the text out of the editor, leaving it empty
+    pub fn take(&mut self) -> String {
+        std::mem::take(self).text
+    }
+
+    /// Apply an editing action, returning `false` if it is not one
+    pub fn handle(&mut self, action: &Action) -> bool {
+        match action {
+            Action::Input(c) => self.insert(c.encode_utf8(&mut [0; 4])),
+            // Pasted text is a single line, so line breaks become spaces
+            Action::Paste(text) => self.insert(&text.replace("\r\n", " ").replace(['\r', '\n', '\t'], " ")),
+            Action::Backspace => {
+                let start = self.prev_boundary();
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            Action::Delete => {
+                let end = self.next_boundary();
+                self.text.replace_range(self.cursor..end, "");
+            }
+            Action::CursorLeft => self.cursor = self.prev_boundary(),
+            Action::CursorRight => self.cursor = self.next_boundary(),
+            Action::Home => self.cursor = 0,
+            Action::End => self.cursor = self.text.len(),
+            Action::DeleteWord => {
+                let start = self.prev_word_start();
+                self.text.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            Action::DeleteToStart => {
+                self.text.replace_range(..self.cursor, "");
+                self.cursor = 0;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The part of the text that fits in `width` columns, and the cursor column within it
+    ///
+    /// Text scrolls horizontally so the cursor always stays visible.
+    pub fn view(&self, width: usize) -> (String, usize) {
+        // Leave a column for the cursor after the last character
+        let width = width.saturating_sub(1);
+        let mut start = 0;
+        while self.text[start..self.cursor].width() > width {
+            start += self.text[start..]
+                .graphemes(true)
+                .next()
+                .map_or(self.cursor - start, str::len);
+        }
+
+        let mut visible = String::new();
+        let mut visible_width = 0;
+        for grapheme in self.text[start..].graphemes(true) {
+            visible_width += grapheme.width();
+            if visible_width > width {
+                break;
+            }
+            visible.push_str(grapheme);
+        }
+        (visible, self.text[start..self.cursor].width())
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    /// Byte offset of the grapheme boundary before the cursor
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(i, _)| i)
+    }
+
+    /// Byte offset of the grapheme boundary after the cursor
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    /// Start of the word before the cursor, skipping any whitespace first
+    fn prev_word_start(&self) -> usize {
+        let before = &self.text[..self.cursor];
+        let trimmed = before.trim_end();
+        trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8())
+    }
+}
@@ -0,0 +1,210 @@
+//! How closely pomodoro estimates matched the pomodoros tasks actually took
+
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use uuid::Uuid;
+
+use crate::plan::Plan;
+use crate::session::Session;
+use crate::text::{pad, truncate};
+
+/// Width of the longest bar in the text report
+const BAR_WIDTH: usize = 20;
+
+/// A finished task with an estimate, and the pomodoros it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskAccuracy {
+    pub id: Uuid,
+    pub title: String,
+    pub estimate: u32,
+    /// Completed pomodoros recorded against the task
+    pub actual: u32,
+    /// Local date of the task's last session, or its planned date without sessions
+    pub finished_on: NaiveDate,
+}
+
+impl TaskAccuracy {
+    /// How far the actual count was from the estimate, as a percentage of the estimate
+    ///
+    /// Positive when the task took longer than estimated.
+    pub fn error_percent(&self) -> f64 {
+        (self.actual as f64 - self.estimate as f64) / self.estimate as f64 * 100.0
+    }
+}
+
+/// Average estimation error of the tasks finished in one week
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeekAccuracy {
+    /// Monday the week starts on
+    pub week: NaiveDate,
+    pub tasks: u32,
+    pub average_error: f64,
+}
+
+/// Estimation accuracy of finished planned tasks, newest first
+#[derive(Debug, Clone, Default)]
+pub struct EstimateReport {
+    pub tasks: Vec<TaskAccuracy>,
+}
+
+impl EstimateReport {
+    /// Compare the estimate of each finished task in `plan` with the sessions linked to it
+    pub fn new(plan: &Plan, sessions: &[Session]) -> Self {
+        let mut tasks: Vec<TaskAccuracy> = plan
+            .tasks
+            .iter()
+            .filter(|task| task.done)
+            .filter_map(|task| {
+                let estimate = task.estimate.filter(|estimate| *estimate > 0)?;
+                let linked = sessions.iter().filter(|session| session.task_id == Some(task.id));
+                let finished_on = linked
+                    .clone()
+                    .map(|session| session.started_at.with_timezone(&Local).date_naive())
+                    .max()
+                    .unwrap_or(task.date);
+                Some(TaskAccuracy {
+                    id: task.id,
+                    title: task.title.clone(),
+                    estimate,
+                    actual: linked.filter(|session| session.completed).count() as u32,
+                    finished_on,
+                })
+            })
+            .collect();
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.finished_on));
+        Self { tasks }
+    }
+
+    /// Keep only tasks finished between `from` and `to`, inclusive
+    pub fn between(mut self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        self.tasks.retain(|task| {
+            from.is_none_or(|from| task.finished_on >= from) && to.is_none_or(|to| task.finished_on <= to)
+        });
+        self
+    }
+
+    /// Keep only tasks worked on in at least one of `sessions`
+    pub fn worked_in(mut self, sessions: &[&Session]) -> Self {
+        self.tasks
+            .retain(|task| sessions.iter().any(|session| session.task_id == Some(task.id)));
+        self
+    }
+
+    /// Mean error over all tasks, or `None` without any
+    pub fn average_error(&self) -> Option<f64> {
+        average(self.tasks.iter())
+    }
+
+    /// Mean error per week with finished tasks, oldest first
+    pub fn by_week(&self) -> Vec<WeekAccuracy> {
+        let mut weeks: Vec<NaiveDate> = self.tasks.iter().map(|task| week_start(task.finished_on)).collect();
+        weeks.sort();
+        weeks.dedup();
+        weeks
+            .into_iter()
+            .filter_map(|week| {
+                let tasks: Vec<&TaskAccuracy> = self
+                    .tasks
+                    .iter()
+                    .filter(|task| week_start(task.finished_on) == week)
+                    .collect();
+                Some(WeekAccuracy {
+                    week,
+                    tasks: tasks.len() as u32,
+                    average_error: average(tasks.into_iter())?,
+                })
+            })
+            .collect()
+    }
+
+    /// Plain-text report: summary, the tasks, then the weekly trend
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let Some(average) = self.average_error() else {
+            let _ = writeln!(out, "No finished tasks with an estimate yet.");
+            let _ = writeln!(out, "Estimate a task in the Plan tab, or end the task prompt with ~N.");
+            return out;
+        };
+
+        let _ = writeln!(
+            out,
+            "{} across {}",
+            capitalize(&describe(average)),
+            format_tasks(self.tasks.len() as u32)
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{:<10}  {:<36} {:>4} {:>7} {:>7}", "Finished", "Task", "Est", "Actual", "Error");
+        for task in &self.tasks {
+            let _ = writeln!(
+                out,
+                "{:<10}  {} {:>4} {:>7} {:>+6.0}%",
+                task.finished_on.format("%Y-%m-%d"),
+                pad(&truncate(&task.title, 36), 36),
+                task.estimate,
+                task.actual,
+                task.error_percent()
+            );
+        }
+
+        let weeks = self.by_week();
+        let max = weeks
+            .iter()
+            .map(|week| week.average_error.abs())
+            .fold(0.0, f64::max)
+            .max(1.0);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "By week (+ took longer than estimated, - shorter)");
+        for week in weeks {
+            let filled = ((week.average_error.abs() / max) * BAR_WIDTH as f64).round() as usize;
+            let bar = if week.average_error < 0.0 { "-" } else { "+" }.repeat(filled);
+            let _ = writeln!(
+                out,
+                "{}  {:<width$} {:>+5.0}%  ({})",
+                week.week.format("%Y-%m-%d"),
+                bar,
+                week.average_error,
+                format_tasks(week.tasks),
+                width = BAR_WIDTH
+            );
+        }
+        out
+    }
+}
+
+/// Describe an average error, e.g. "underestimated by 40% on average"
+pub fn describe(average_error: f64) -> String {
+    let percent = average_error.abs().round();
+    if percent == 0.0 {
+        "estimates were spot on on average".to_string()
+    } else if average_error > 0.0 {
+        format!("underestimated by {:.0}% on average", percent)
+    } else {
+        format!("overestimated by {:.0}% on average", percent)
+    }
+}
+
+/// Task count with its unit, e.g. "1 task" or "3 tasks"
+pub fn format_tasks(count: u32) -> String {
+    if count == 1 {
+        "1 task".to_string()
+    } else {
+        format!("{} tasks", count)
+    }
+}
+
+fn average<'a>(tasks: impl Iterator<Item = &'a TaskAccuracy>) -> Option<f64> {
+    let (sum, count) = tasks.fold((0.0, 0), |(sum, count), task| (sum + task.error_percent(), count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
@@ -0,0 +1,244 @@
+//! Export sessions as CSV, JSON, JSON Lines or iCalendar for spreadsheets, timesheets and calendars
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::{Context, Result};
+use serde_json::{Map, Value};
+
+use crate::components::session_list::SessionQuery;
+use crate::ical;
+use crate::session::Session;
+use crate::storage::Storage;
+
+/// How often `--watch` checks the sessions file for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Output format for `tomatocrab export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Jsonl,
+    /// iCalendar, one event per session (columns are ignored)
+    Ics,
+}
+
+/// A field that can be included in an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Column {
+    Id,
+    Date,
+    Start,
+    End,
+    Task,
+    Tags,
+    #[value(name = "duration_secs")]
+    DurationSecs,
+    #[value(name = "duration_min")]
+    DurationMin,
+    Completed,
+    Manual,
+}
+
+/// Columns exported when none are requested explicitly
+pub const DEFAULT_COLUMNS: [Column; 8] = [
+    Column::Id,
+    Column::Start,
+    Column::End,
+    Column::Task,
+    Column::Tags,
+    Column::DurationSecs,
+    Column::Completed,
+    Column::Manual,
+];
+
+/// Timezone used for exported timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Timestamps {
+    Local,
+    Utc,
+}
+
+impl ExportFormat {
+    /// Usual file extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+impl Column {
+    /// Header / key name used in the output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Date => "date",
+            Column::Start => "start",
+            Column::End => "end",
+            Column::Task => "task",
+            Column::Tags => "tags",
+            Column::DurationSecs => "duration_secs",
+            Column::DurationMin => "duration_min",
+            Column::Completed => "completed",
+            Column::Manual => "manual",
+        }
+    }
+
+    /// Structured value of this column for a session
+    fn value(&self, session: &Session, timestamps: Timestamps) -> Value {
+        match self {
+            Column::Id => Value::from(session.id.to_string()),
+            Column::Date => {
+                let date = match timestamps {
+                    Timestamps::Local => session.started_at.with_timezone(&Local).date_naive(),
+                    Timestamps::Utc => session.started_at.date_naive(),
+                };
+                Value::from(date.to_string())
+            }
+            Column::Start => Value::from(format_timestamp(session.started_at, timestamps)),
+            Column::End => Value::from(format_timestamp(session.ended_at(), timestamps)),
+            Column::Task => Value::from(session.task.clone()),
+            Column::Tags => Value::from(session.tags()),
+            Column::DurationSecs => Value::from(session.duration_secs),
+            Column::DurationMin => Value::from((session.duration_secs as f64 / 60.0 * 100.0).round() / 100.0),
+            Column::Completed => Value::from(session.completed),
+            Column::Manual => Value::from(session.manual),
+        }
+    }
+
+    /// Flat text value of this column, as written to CSV
+    fn text(&self, session: &Session, timestamps: Timestamps) -> String {
+        match self.value(session, timestamps) {
+            Value::String(text) => text,
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Options controlling the shape of an export
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub columns: Vec<Column>,
+    pub timestamps: Timestamps,
+}
+
+/// Write sessions to `writer` in the requested format
+pub fn export_sessions<W: Write>(sessions: &[&Session], options: &ExportOptions, mut writer: W) -> Result<()> {
+    match options.format {
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(&mut writer);
+            csv_writer
+                .write_record(options.columns.iter().map(|column| column.name()))
+                .wrap_err("Failed to write CSV header")?;
+            for session in sessions {
+                let record = options
+                    .columns
+                    .iter()
+                    .map(|column| column.text(session, options.timestamps));
+                csv_writer.write_record(record).wrap_err("Failed to write CSV row")?;
+            }
+            csv_writer.flush().wrap_err("Failed to flush CSV output")?;
+        }
+        ExportFormat::Json => {
+            let records: Vec<Value> = sessions.iter().map(|session| record(session, options)).collect();
+            serde_json::to_writer_pretty(&mut writer, &records).wrap_err("Failed to write JSON")?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Jsonl => {
+            for session in sessions {
+                serde_json::to_writer(&mut writer, &record(session, options))
+                    .wrap_err("Failed to write JSON line")?;
+                writeln!(writer)?;
+            }
+        }
+        ExportFormat::Ics => ical::write_calendar(sessions, &mut writer)?,
+    }
+
+    writer.flush().wrap_err("Failed to flush export output")?;
+    Ok(())
+}
+
+/// Export sessions to a file, replacing it atomically so readers never see a partial file
+pub fn export_to_file(sessions: &[&Session], options: &ExportOptions, path: &Path) -> Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = Path::new(&tmp_name);
+
+    let file = File::create(tmp_path)
+        .wrap_err_with(|| format!("Failed to create {}", tmp_path.display()))?;
+    export_sessions(sessions, options, BufWriter::new(file))?;
+    fs::rename(tmp_path, path).wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Keep `path` up to date with the stored sessions until interrupted
+///
+/// The file is rewritten whenever the sessions file changes, and at midnight so
+/// relative filters like `--today` roll over.
+pub fn watch(storage: &Storage, query: &SessionQuery, options: &ExportOptions, path: &Path) -> Result<()> {
+    let mut last_seen: Option<(Option<SystemTime>, chrono::NaiveDate)> = None;
+
+    eprintln!("Watching for new sessions, writing {} (Ctrl-C to stop)", path.display());
+    loop {
+        let modified = fs::metadata(storage.data_path())
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let current = (modified, Local::now().date_naive());
+
+        if last_seen != Some(current) {
+            // The timer may be mid-write; leave `last_seen` alone so the next tick retries
+            let sessions = match storage.load_sessions() {
+                Ok(sessions) => sessions,
+                Err(err) => {
+                    eprintln!("[{}] Could not read sessions: {}", Local::now().format("%H:%M:%S"), err);
+                    thread::sleep(WATCH_INTERVAL);
+                    continue;
+                }
+            };
+            let filtered = query.apply(&sessions);
+            export_to_file(&filtered, options, path)?;
+            eprintln!(
+                "[{}] Wrote {} sessions",
+                Local::now().format("%H:%M:%S"),
+                filtered.len()
+            );
+            last_seen = Some(current);
+        }
+
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Build a JSON object holding the selected columns of a session
+fn record(session: &Session, options: &ExportOptions) -> Value {
+    let map: Map<String, Value> = options
+        .columns
+        .iter()
+        .map(|column| (column.name().to_string(), column.value(session, options.timestamps)))
+        .collect();
+    Value::Object(map)
+}
+
+/// Format a timestamp as RFC 3339 in the requested timezone
+fn format_timestamp(time: DateTime<Utc>, timestamps: Timestamps) -> String {
+    match timestamps {
+        Timestamps::Local => time.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::Secs, false),
+        Timestamps::Utc => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+    }
+}
@@ -0,0 +1,176 @@
+//! Daily focus goals and streaks of days that met them
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use serde::Deserialize;
+
+use crate::session::Session;
+
+/// Pomodoro length used to compare pomodoro goals with focus time, e.g. in the heatmap
+pub const DEFAULT_POMODORO_SECS: u32 = 25 * 60;
+
+/// Amount of focus a day should reach
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GoalTarget {
+    /// Completed pomodoros
+    Pomodoros(u32),
+    /// Minutes of focus time, whether or not the sessions completed
+    Minutes(u32),
+}
+
+impl GoalTarget {
+    /// The target number of pomodoros or minutes
+    pub fn amount(&self) -> u32 {
+        match self {
+            GoalTarget::Pomodoros(amount) | GoalTarget::Minutes(amount) => *amount,
+        }
+    }
+
+    /// Progress made by a day's totals, in the goal's own unit
+    pub fn achieved(&self, totals: DayTotals) -> u32 {
+        match self {
+            GoalTarget::Pomodoros(_) => totals.pomodoros,
+            GoalTarget::Minutes(_) => totals.focus_secs / 60,
+        }
+    }
+
+    /// Target expressed as focus time, counting a pomodoro as `pomodoro_secs`
+    ///
+    /// Saturates, so an absurdly large goal is simply never met.
+    pub fn secs(&self, pomodoro_secs: u32) -> u32 {
+        match self {
+            GoalTarget::Pomodoros(amount) => amount.saturating_mul(pomodoro_secs),
+            GoalTarget::Minutes(amount) => amount.saturating_mul(60),
+        }
+    }
+
+    /// Name of the unit, pluralised for `amount`
+    pub fn unit(&self) -> &'static str {
+        match self {
+            GoalTarget::Pomodoros(1) => "pomodoro",
+            GoalTarget::Pomodoros(_) => "pomodoros",
+            GoalTarget::Minutes(_) => "min",
+        }
+    }
+}
+
+/// Daily goal with optional per-weekday overrides, from the `[goal]` config table
+///
+/// A weekday set to zero is a rest day: it neither extends nor breaks a streak.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DailyGoal {
+    pub default: GoalTarget,
+    pub monday: Option<GoalTarget>,
+    pub tuesday: Option<GoalTarget>,
+    pub wednesday: Option<GoalTarget>,
+    pub thursday: Option<GoalTarget>,
+    pub friday: Option<GoalTarget>,
+    pub saturday: Option<GoalTarget>,
+    pub sunday: Option<GoalTarget>,
+}
+
+impl Default for DailyGoal {
+    fn default() -> Self {
+        Self {
+            default: GoalTarget::Pomodoros(4),
+            monday: None,
+            tuesday: None,
+            wednesday: None,
+            thursday: None,
+            friday: None,
+            saturday: None,
+            sunday: None,
+        }
+    }
+}
+
+/// Completed pomodoros and focus time on one day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DayTotals {
+    pub pomodoros: u32,
+    pub focus_secs: u32,
+}
+
+/// Runs of consecutive days that met the goal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    /// Days in the run ending today, or yesterday while today is still in progress
+    pub current: u32,
+    pub longest: u32,
+}
+
+impl DailyGoal {
+    /// Goal for a day of the week
+    pub fn target(&self, weekday: Weekday) -> GoalTarget {
+        let day = match weekday {
+            Weekday::Mon => self.monday,
+            Weekday::Tue => self.tuesday,
+            Weekday::Wed => self.wednesday,
+            Weekday::Thu => self.thursday,
+            Weekday::Fri => self.friday,
+            Weekday::Sat => self.saturday,
+            Weekday::Sun => self.sunday,
+        };
+        day.unwrap_or(self.default)
+    }
+
+    /// Goal for each weekday from Monday as focus time, counting a pomodoro as `pomodoro_secs`
+    pub fn weekly_secs(&self, pomodoro_secs: u32) -> [u32; 7] {
+        let mut secs = [0; 7];
+        let mut weekday = Weekday::Mon;
+        for slot in &mut secs {
+            *slot = self.target(weekday).secs(pomodoro_secs);
+            weekday = weekday.succ();
+        }
+        secs
+    }
+
+    /// Whether `totals` meet the goal for `date`, or `None` on a rest day
+    pub fn met(&self, date: NaiveDate, totals: DayTotals) -> Option<bool> {
+        let target = self.target(date.weekday());
+        (target.amount() > 0).then(|| target.achieved(totals) >= target.amount())
+    }
+
+    /// Current and longest streaks of days meeting the goal, up to `today`
+    pub fn streaks<'a>(&self, sessions: impl IntoIterator<Item = &'a Session>, today: NaiveDate) -> Streaks {
+        let totals = day_totals(sessions);
+        let Some(&first) = totals.keys().min() else {
+            return Streaks::default();
+        };
+
+        let mut streaks = Streaks::default();
+        let mut run = 0;
+        let mut date = first;
+        while date <= today {
+            match self.met(date, totals.get(&date).copied().unwrap_or_default()) {
+                Some(true) => run += 1,
+                // An unfinished today doesn't break the run
+                Some(false) if date == today => {}
+                Some(false) => run = 0,
+                None => {}
+            }
+            streaks.longest = streaks.longest.max(run);
+            date += Duration::days(1);
+        }
+        streaks.current = run;
+        streaks
+    }
+}
+
+/// Completed pomodoros and focus time per local day
+pub fn day_totals<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> HashMap<NaiveDate, DayTotals> {
+    let mut totals: HashMap<NaiveDate, DayTotals> = HashMap::new();
+    for session in sessions {
+        let day = totals
+            .entry(session.started_at.with_timezone(&Local).date_naive())
+            .or_default();
+        day.focus_secs += session.duration_secs;
+        if session.completed {
+            day.pomodoros += 1;
+        }
+    }
+    totals
+}
@@ -0,0 +1,146 @@
+//! Year-long calendar heatmap of daily focus time
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+use crate::session::Session;
+
+/// Number of week columns in the heatmap
+pub const WEEKS: usize = 53;
+
+/// Shade characters used by the plain-text heatmap, from no focus to well past the goal
+const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Focus time per day over the year ending with the current week
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    /// Monday of the first week shown
+    pub start: NaiveDate,
+    /// Last day with data (today)
+    pub end: NaiveDate,
+    /// Daily focus goal in seconds for each weekday from Monday
+    goals: [u32; 7],
+    focus: HashMap<NaiveDate, u32>,
+}
+
+impl Heatmap {
+    /// Total up focus time per local day for the year ending on `end`
+    ///
+    /// `goals` holds the daily goal in seconds for each weekday from Monday.
+    pub fn new<'a>(sessions: impl IntoIterator<Item = &'a Session>, end: NaiveDate, goals: [u32; 7]) -> Self {
+        let monday = end - Duration::days(end.weekday().num_days_from_monday() as i64);
+        let start = monday - Duration::weeks(WEEKS as i64 - 1);
+
+        let mut focus = HashMap::new();
+        for session in sessions {
+            let date = session.started_at.with_timezone(&Local).date_naive();
+            if (start..=end).contains(&date) {
+                *focus.entry(date).or_insert(0) += session.duration_secs;
+            }
+        }
+
+        Self {
+            start,
+            end,
+            goals,
+            focus,
+        }
+    }
+
+    /// Focus seconds on `date`
+    pub fn focus_secs(&self, date: NaiveDate) -> u32 {
+        self.focus.get(&date).copied().unwrap_or(0)
+    }
+
+    /// Daily goal in seconds for `date`
+    pub fn goal_secs(&self, date: NaiveDate) -> u32 {
+        self.goals[date.weekday().num_days_from_monday() as usize]
+    }
+
+    /// Color bucket for `date`: 0 for no focus, 1-2 below the goal, 3 at it, 4 at 150% or more
+    pub fn level(&self, date: NaiveDate) -> u8 {
+        let secs = self.focus_secs(date);
+        if secs == 0 {
+            return 0;
+        }
+        let ratio = secs as f64 / self.goal_secs(date).max(1) as f64;
+        match ratio {
+            r if r < 0.5 => 1,
+            r if r < 1.0 => 2,
+            r if r < 1.5 => 3,
+            _ => 4,
+        }
+    }
+
+    /// Day at `weekday` (0 = Monday) of week column `week`, if it is not in the future
+    pub fn date_at(&self, week: usize, weekday: usize) -> Option<NaiveDate> {
+        let date = self.start + Duration::days((week * 7 + weekday) as i64);
+        (date <= self.end).then_some(date)
+    }
+
+    /// Week column containing `date`
+    pub fn week_of(&self, date: NaiveDate) -> usize {
+        ((date - self.start).num_days().max(0) / 7) as usize
+    }
+
+    /// Whether `date` falls inside the heatmap
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.start..=self.end).contains(&date)
+    }
+
+    /// Month label to print above week column `week`, if a month starts in it
+    pub fn month_label(&self, week: usize) -> Option<String> {
+        let first = self.start + Duration::days(week as i64 * 7);
+        let last = first + Duration::days(6);
+        if week == 0 || first.month() != last.month() || first.day() == 1 {
+            Some(last.format("%b").to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Render the heatmap as text, one row per weekday, using `paint` to color each cell
+    pub fn render_text(&self, paint: impl Fn(char, u8) -> String) -> String {
+        let mut out = String::new();
+
+        // Month names, each starting above its first week
+        let mut header = String::from("    ");
+        let mut week = 0;
+        while week < WEEKS {
+            match self.month_label(week) {
+                Some(label) if week + 1 < WEEKS => {
+                    header.push_str(&label);
+                    header.push(' ');
+                    week += 2;
+                }
+                _ => {
+                    header.push_str("  ");
+                    week += 1;
+                }
+            }
+        }
+        let _ = writeln!(out, "{}", header.trim_end());
+
+        for (weekday, name) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+            let _ = write!(out, "{:<4}", name);
+            for week in 0..WEEKS {
+                match self.date_at(week, weekday) {
+                    Some(date) => {
+                        let level = self.level(date);
+                        let _ = write!(out, "{} ", paint(SHADES[level as usize], level));
+                    }
+                    None => out.push_str("  "),
+                }
+            }
+            let _ = writeln!(out);
+        }
+
+        let legend: Vec<String> = (0..SHADES.len() as u8)
+            .map(|level| paint(SHADES[level as usize], level))
+            .collect();
+        let _ = writeln!(out, "    Less {} More   (measured against the daily goal)", legend.join(" "));
+        out
+    }
+}
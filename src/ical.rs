@@ -0,0 +1,103 @@
+//! iCalendar (.ics) rendering of sessions so focus time shows up in calendar apps
+
+use std::io::Write;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+
+use crate::session::Session;
+
+/// Maximum length of a content line in octets before it must be folded (RFC 5545 §3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Write sessions as a VCALENDAR containing one VEVENT per session
+pub fn write_calendar<W: Write>(sessions: &[&Session], mut writer: W) -> Result<()> {
+    write_line(&mut writer, "BEGIN:VCALENDAR")?;
+    write_line(&mut writer, "VERSION:2.0")?;
+    write_line(&mut writer, "PRODID:-//tomatocrab//tomatocrab//EN")?;
+    write_line(&mut writer, "CALSCALE:GREGORIAN")?;
+    write_line(&mut writer, "X-WR-CALNAME:Focus sessions")?;
+
+    for session in sessions {
+        write_event(&mut writer, session)?;
+    }
+
+    write_line(&mut writer, "END:VCALENDAR")?;
+    Ok(())
+}
+
+/// Write a single session as a VEVENT
+fn write_event<W: Write>(writer: &mut W, session: &Session) -> Result<()> {
+    let minutes = session.duration_secs / 60;
+    let (summary, description) = if session.completed {
+        (escape_text(&session.task), format!("Completed pomodoro ({} min)", minutes))
+    } else {
+        (
+            format!("{} (interrupted)", escape_text(&session.task)),
+            format!("Interrupted after {} min", minutes),
+        )
+    };
+
+    write_line(writer, "BEGIN:VEVENT")?;
+    write_line(writer, &format!("UID:{}@tomatocrab", session.id))?;
+    // Derived from the session rather than the export time so output is stable between refreshes
+    write_line(writer, &format!("DTSTAMP:{}", format_time(session.ended_at())))?;
+    write_line(writer, &format!("DTSTART:{}", format_time(session.started_at)))?;
+    write_line(writer, &format!("DTEND:{}", format_time(session.ended_at())))?;
+    write_line(writer, &format!("SUMMARY:{}", summary))?;
+    write_line(writer, &format!("DESCRIPTION:{}", escape_text(&description)))?;
+    write_line(writer, "STATUS:CONFIRMED")?;
+    write_line(writer, "TRANSP:OPAQUE")?;
+    let tags = session.tags();
+    if !tags.is_empty() {
+        let categories: Vec<String> = tags.iter().map(|tag| escape_text(tag)).collect();
+        write_line(writer, &format!("CATEGORIES:{}", categories.join(",")))?;
+    }
+    write_line(
+        writer,
+        &format!(
+            "X-TOMATOCRAB-COMPLETED:{}",
+            if session.completed { "TRUE" } else { "FALSE" }
+        ),
+    )?;
+    write_line(writer, "END:VEVENT")?;
+    Ok(())
+}
+
+/// Format a UTC timestamp in iCalendar basic format
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT property value
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write a content line with CRLF endings, folding it at 75 octets without splitting characters
+fn write_line<W: Write>(writer: &mut W, line: &str) -> Result<()> {
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts toward their length
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            writer.write_all(b"\r\n ")?;
+            octets = 1;
+        }
+        let mut buf = [0; 4];
+        writer.write_all(c.encode_utf8(&mut buf).as_bytes())?;
+        octets += c.len_utf8();
+    }
+    writer.write_all(b"\r\n")?;
+    Ok(())
+}
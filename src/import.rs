@@ -0,0 +1,303 @@
+//! Import sessions from CSV files, Toggl exports and Timewarrior exports
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::{bail, eyre, Context, Result};
+use serde::Deserialize;
+
+use crate::parse;
+use crate::session::Session;
+
+/// Source format for `tomatocrab import`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Generic CSV with configurable column mapping
+    Csv,
+    /// Toggl Track detailed CSV export
+    Toggl,
+    /// Output of `timew export`
+    Timewarrior,
+}
+
+/// A session field that can be read from a CSV column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Field {
+    Task,
+    Start,
+    End,
+    /// Duration as `25m`, `1h30m` or `H:MM:SS`
+    Duration,
+    #[value(name = "duration_secs")]
+    DurationSecs,
+    #[value(name = "duration_min")]
+    DurationMin,
+    Completed,
+}
+
+impl Field {
+    /// Column name used when no mapping is given (matches `tomatocrab export`)
+    fn default_column(&self) -> &'static str {
+        match self {
+            Field::Task => "task",
+            Field::Start => "start",
+            Field::End => "end",
+            Field::Duration => "duration",
+            Field::DurationSecs => "duration_secs",
+            Field::DurationMin => "duration_min",
+            Field::Completed => "completed",
+        }
+    }
+}
+
+/// Parse a `FIELD=COLUMN` mapping argument
+pub fn parse_mapping(input: &str) -> Result<(Field, String), String> {
+    let (field, column) = input
+        .split_once('=')
+        .ok_or_else(|| format!("invalid mapping '{}' (expected FIELD=COLUMN)", input))?;
+    let field = Field::from_str(field.trim(), true)
+        .map_err(|_| format!("unknown field '{}' in mapping '{}'", field, input))?;
+    Ok((field, column.trim().to_string()))
+}
+
+/// Outcome of matching imported sessions against existing ones
+pub struct ImportPlan {
+    /// Sessions that are not yet stored
+    pub new_sessions: Vec<Session>,
+    /// Number of sessions skipped because they were already present
+    pub duplicates: usize,
+}
+
+/// Read sessions from a file in the given format
+pub fn read_sessions(path: &Path, format: ImportFormat, mappings: &[(Field, String)]) -> Result<Vec<Session>> {
+    let content =
+        fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+
+    match format {
+        ImportFormat::Csv => read_csv(&content, mappings),
+        ImportFormat::Toggl => read_toggl(&content),
+        ImportFormat::Timewarrior => read_timewarrior(&content),
+    }
+}
+
+/// Drop sessions that already exist (same start second and task), including repeats within the import
+pub fn plan_import(existing: &[Session], imported: Vec<Session>) -> ImportPlan {
+    let mut seen: HashSet<(i64, String)> = existing.iter().map(dedup_key).collect();
+    let mut new_sessions = Vec::new();
+    let mut duplicates = 0;
+
+    for session in imported {
+        let key = dedup_key(&session);
+        if seen.insert(key) {
+            new_sessions.push(session);
+        } else {
+            duplicates += 1;
+        }
+    }
+
+    ImportPlan {
+        new_sessions,
+        duplicates,
+    }
+}
+
+/// Identity used for deduplication
+fn dedup_key(session: &Session) -> (i64, String) {
+    (session.started_at.timestamp(), session.task.trim().to_string())
+}
+
+/// Read a generic CSV file using the given field-to-column mapping
+fn read_csv(content: &str, mappings: &[(Field, String)]) -> Result<Vec<Session>> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader.headers().wrap_err("Failed to read CSV header")?.clone();
+
+    let overrides: HashMap<Field, &str> = mappings
+        .iter()
+        .map(|(field, column)| (*field, column.as_str()))
+        .collect();
+    let column_index = |field: Field| {
+        let name = overrides.get(&field).copied().unwrap_or(field.default_column());
+        headers.iter().position(|header| header.trim() == name)
+    };
+
+    let task_col = column_index(Field::Task).ok_or_else(|| missing_column(Field::Task))?;
+    let start_col = column_index(Field::Start).ok_or_else(|| missing_column(Field::Start))?;
+    let end_col = column_index(Field::End);
+    let duration_col = column_index(Field::Duration);
+    let duration_secs_col = column_index(Field::DurationSecs);
+    let duration_min_col = column_index(Field::DurationMin);
+    let completed_col = column_index(Field::Completed);
+
+    let mut sessions = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = row + 2;
+        let record = record.wrap_err_with(|| format!("Failed to read CSV line {}", line))?;
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|index| record.get(index))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let task = cell(Some(task_col)).ok_or_else(|| eyre!("Line {}: task is empty", line))?;
+        let started_at = cell(Some(start_col))
+            .ok_or_else(|| eyre!("Line {}: start is empty", line))
+            .and_then(|value| parse::parse_timestamp(value).map_err(|e| eyre!("Line {}: {}", line, e)))?;
+
+        let duration_secs = if let Some(value) = cell(duration_secs_col) {
+            value
+                .parse::<u32>()
+                .map_err(|_| eyre!("Line {}: invalid duration_secs '{}'", line, value))?
+        } else if let Some(value) = cell(duration_min_col) {
+            let secs = value
+                .parse::<f64>()
+                .ok()
+                .map(|minutes| (minutes * 60.0).round())
+                .filter(|secs| secs.is_finite() && *secs > 0.0 && *secs <= f64::from(u32::MAX))
+                .ok_or_else(|| eyre!("Line {}: invalid duration_min '{}'", line, value))?;
+            secs as u32
+        } else if let Some(value) = cell(duration_col) {
+            parse::parse_clock_duration(value)
+                .or_else(|_| parse::parse_duration(value))
+                .map_err(|e| eyre!("Line {}: {}", line, e))?
+        } else if let Some(value) = cell(end_col) {
+            let ended_at = parse::parse_timestamp(value).map_err(|e| eyre!("Line {}: {}", line, e))?;
+            span_secs(started_at, ended_at).ok_or_else(|| eyre!("Line {}: end is before start", line))?
+        } else {
+            bail!("Line {}: no end time or duration", line);
+        };
+        if duration_secs == 0 {
+            bail!("Line {}: session has no duration", line);
+        }
+
+        let completed = match cell(completed_col) {
+            Some(value) => parse_bool(value).ok_or_else(|| eyre!("Line {}: invalid completed '{}'", line, value))?,
+            None => true,
+        };
+
+        sessions.push(Session::new(task.to_string(), started_at, duration_secs, completed));
+    }
+
+    Ok(sessions)
+}
+
+/// A row of a Toggl Track detailed report export
+#[derive(Deserialize)]
+struct TogglRow {
+    #[serde(rename = "Description", default)]
+    description: String,
+    #[serde(rename = "Project", default)]
+    project: String,
+    #[serde(rename = "Start date")]
+    start_date: String,
+    #[serde(rename = "Start time")]
+    start_time: String,
+    #[serde(rename = "Duration")]
+    duration: String,
+    #[serde(rename = "Tags", default)]
+    tags: String,
+}
+
+/// Read a Toggl Track CSV export (times are in local time)
+fn read_toggl(content: &str) -> Result<Vec<Session>> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let mut sessions = Vec::new();
+
+    for (row, record) in reader.deserialize::<TogglRow>().enumerate() {
+        let line = row + 2;
+        let record = record.wrap_err_with(|| format!("Failed to read Toggl line {}", line))?;
+
+        let started_at = parse::parse_timestamp(&format!("{} {}", record.start_date, record.start_time))
+            .map_err(|e| eyre!("Line {}: {}", line, e))?;
+        let duration_secs =
+            parse::parse_clock_duration(&record.duration).map_err(|e| eyre!("Line {}: {}", line, e))?;
+
+        let mut task = if record.description.trim().is_empty() {
+            record.project.trim().to_string()
+        } else {
+            record.description.trim().to_string()
+        };
+        for tag in record.tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            task.push_str(&format!(" #{}", tag.replace(' ', "-")));
+        }
+        if task.is_empty() {
+            task = "(no description)".to_string();
+        }
+
+        sessions.push(Session::new(task, started_at, duration_secs, true));
+    }
+
+    Ok(sessions)
+}
+
+/// An interval from `timew export`
+#[derive(Deserialize)]
+struct TimewarriorInterval {
+    start: String,
+    end: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    annotation: Option<String>,
+}
+
+/// Read the JSON produced by `timew export`, skipping intervals that are still open
+fn read_timewarrior(content: &str) -> Result<Vec<Session>> {
+    let intervals: Vec<TimewarriorInterval> =
+        serde_json::from_str(content).wrap_err("Failed to parse Timewarrior export")?;
+
+    let mut sessions = Vec::new();
+    for interval in intervals {
+        let Some(end) = interval.end else {
+            continue;
+        };
+        let started_at = parse_timewarrior_time(&interval.start)?;
+        let ended_at = parse_timewarrior_time(&end)?;
+        let Some(duration_secs) = span_secs(started_at, ended_at) else {
+            continue;
+        };
+
+        let task = match interval.annotation.filter(|a| !a.trim().is_empty()) {
+            Some(annotation) => annotation.trim().to_string(),
+            None if !interval.tags.is_empty() => interval.tags.join(", "),
+            None => "(no description)".to_string(),
+        };
+
+        sessions.push(Session::new(task, started_at, duration_secs, true));
+    }
+
+    Ok(sessions)
+}
+
+/// Parse Timewarrior's compact UTC timestamp format (`20240501T090000Z`)
+fn parse_timewarrior_time(input: &str) -> Result<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(input, "%Y%m%dT%H%M%SZ")
+        .map(|naive| naive.and_utc())
+        .map_err(|_| eyre!("Invalid Timewarrior timestamp '{}'", input))
+}
+
+/// Seconds between two instants, or `None` if they are out of order
+fn span_secs(start: DateTime<Utc>, end: DateTime<Utc>) -> Option<u32> {
+    u32::try_from((end - start).num_seconds()).ok()
+}
+
+/// Parse a loose boolean as written by spreadsheets and other trackers
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "completed" => Some(true),
+        "false" | "no" | "n" | "0" | "interrupted" => Some(false),
+        _ => None,
+    }
+}
+
+/// Error for a required CSV column that could not be found
+fn missing_column(field: Field) -> color_eyre::eyre::Report {
+    eyre!(
+        "CSV has no '{}' column; map one with --map {}=COLUMN",
+        field.default_column(),
+        field.default_column()
+    )
+}
@@ -0,0 +1,135 @@
+//! Adapters for external task and time trackers
+//!
+//! The Taskwarrior and Timewarrior adapters shell out to the tool's command
+//! line program, whose path is configurable so a stand-in script can be used
+//! instead of the real binary. The todo.txt adapter reads and writes the file
+//! directly.
+
+pub mod taskwarrior;
+pub mod timewarrior;
+pub mod todo_txt;
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::{bail, eyre, Context, Result};
+
+pub use taskwarrior::{Taskwarrior, TaskwarriorTask};
+pub use timewarrior::Timewarrior;
+pub use todo_txt::{TodoItem, TodoTxt};
+
+/// Integrations enabled for the timer
+#[derive(Debug, Default)]
+pub struct Integrations {
+    /// Pick tasks from and annotate tasks in Taskwarrior
+    pub taskwarrior: Option<Taskwarrior>,
+    /// Record completed work sessions as Timewarrior intervals
+    pub timewarrior: Option<Timewarrior>,
+    /// Take tasks from a todo.txt queue and track pomodoros against estimates
+    pub todo_txt: Option<TodoTxt>,
+}
+
+/// Longest an external program may run before it is killed
+///
+/// The timer calls the trackers from background threads, so this bounds how long a
+/// hung `task` or `timew` lingers rather than how long the UI waits.
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Run an external program and return its standard output
+fn run(bin: &Path, args: &[String]) -> Result<String> {
+    let mut child = Command::new(bin)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .wrap_err_with(|| format!("Failed to run {}", bin.display()))?;
+
+    // Drain the pipes while waiting so a chatty program can't fill them and stall
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("{} did not finish within {} seconds", bin.display(), TIMEOUT.as_secs());
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    let stdout = stdout.join().map_err(|_| eyre!("Failed to read output of {}", bin.display()))?;
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        bail!(
+            "{} exited with {}: {}",
+            bin.display(),
+            status,
+            stderr.trim()
+        );
+    }
+
+    String::from_utf8(stdout).wrap_err_with(|| format!("{} produced invalid UTF-8", bin.display()))
+}
+
+/// Read a child's pipe to the end on another thread
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+#[cfg(all(test, unix))]
+pub(crate) mod test_support {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Held by tests that spawn programs
+    ///
+    /// Another test forking while a stub script is still open for writing would make
+    /// executing it fail with "Text file busy", so stubs are written and run one test
+    /// at a time.
+    static SPAWN_LOCK: Mutex<()> = Mutex::new(());
+
+    pub fn spawn_lock() -> MutexGuard<'static, ()> {
+        SPAWN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// A fresh temporary directory for one test
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tomatocrab-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Write an executable shell script standing in for a tracker's binary
+    pub fn stub_binary(dir: &Path, name: &str, script: &str) -> PathBuf {
+        let bin = dir.join(name);
+        // Created executable and closed before it is run, so no chmod is needed afterwards
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o755)
+            .open(&bin)
+            .unwrap();
+        file.write_all(script.as_bytes()).unwrap();
+        file.sync_all().unwrap();
+        drop(file);
+        bin
+    }
+}
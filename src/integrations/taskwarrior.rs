@@ -0,0 +1,117 @@
+//! Taskwarrior adapter built on `task export` and `task annotate`
+
+use std::path::PathBuf;
+
+use color_eyre::eyre::{Context, Result};
+use serde::Deserialize;
+
+use super::run;
+
+/// A pending task as reported by `task export`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskwarriorTask {
+    pub uuid: String,
+    pub description: String,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub urgency: f64,
+}
+
+/// Shells out to the `task` program
+#[derive(Debug, Clone)]
+pub struct Taskwarrior {
+    bin: PathBuf,
+}
+
+impl Taskwarrior {
+    /// Create an adapter using the given `task` binary
+    pub fn new(bin: impl Into<PathBuf>) -> Self {
+        Self { bin: bin.into() }
+    }
+
+    /// Load pending tasks, most urgent first
+    pub fn pending_tasks(&self) -> Result<Vec<TaskwarriorTask>> {
+        let args = [
+            "rc.verbose=nothing",
+            "rc.confirmation=off",
+            "rc.json.array=on",
+            "status:pending",
+            "export",
+        ];
+        let output = run(&self.bin, &args.map(String::from))?;
+
+        let mut tasks: Vec<TaskwarriorTask> =
+            serde_json::from_str(&output).wrap_err("Failed to parse task export output")?;
+        tasks.sort_by(|a, b| b.urgency.total_cmp(&a.urgency));
+        Ok(tasks)
+    }
+
+    /// Add an annotation to a task
+    pub fn annotate(&self, uuid: &str, text: &str) -> Result<()> {
+        let args = [
+            "rc.verbose=nothing".to_string(),
+            "rc.confirmation=off".to_string(),
+            uuid.to_string(),
+            "annotate".to_string(),
+            "--".to_string(),
+            text.to_string(),
+        ];
+        run(&self.bin, &args)?;
+        Ok(())
+    }
+}
+
+impl TaskwarriorTask {
+    /// Label shown in the task picker
+    pub fn label(&self) -> String {
+        match &self.project {
+            Some(project) => format!("{} [{}]", self.description, project),
+            None => self.description.clone(),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::integrations::test_support::{spawn_lock, stub_binary, temp_dir};
+
+    #[test]
+    fn reads_pending_tasks_and_annotates_through_a_stub_binary() {
+        let _lock = spawn_lock();
+        let dir = temp_dir("taskwarrior");
+        let script = format!(
+            r#"#!/bin/sh
+case "$*" in
+  *export*) echo '[{{"uuid":"a","description":"Low","urgency":1.5}},{{"uuid":"b","description":"High","project":"crab","urgency":9}}]' ;;
+  *annotate*) printf '%s\n' "$@" > "{}" ;;
+esac
+"#,
+            dir.join("args").display()
+        );
+        let bin = stub_binary(&dir, "task", &script);
+
+        let taskwarrior = Taskwarrior::new(&bin);
+        let tasks = taskwarrior.pending_tasks().unwrap();
+        let labels: Vec<String> = tasks.iter().map(TaskwarriorTask::label).collect();
+        assert_eq!(labels, ["High [crab]", "Low"]);
+
+        taskwarrior.annotate("b", "Pomodoro completed (25 min)").unwrap();
+        let args = fs::read_to_string(dir.join("args")).unwrap();
+        assert_eq!(
+            args.lines().collect::<Vec<_>>(),
+            ["rc.verbose=nothing", "rc.confirmation=off", "b", "annotate", "--", "Pomodoro completed (25 min)"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failing_binary_is_an_error() {
+        let _lock = spawn_lock();
+        assert!(Taskwarrior::new("false").pending_tasks().is_err());
+    }
+}
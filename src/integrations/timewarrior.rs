@@ -0,0 +1,74 @@
+//! Timewarrior adapter built on `timew track`
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+
+use super::run;
+
+/// Shells out to the `timew` program
+#[derive(Debug, Clone)]
+pub struct Timewarrior {
+    bin: PathBuf,
+}
+
+impl Timewarrior {
+    /// Create an adapter using the given `timew` binary
+    pub fn new(bin: impl Into<PathBuf>) -> Self {
+        Self { bin: bin.into() }
+    }
+
+    /// Record a closed interval tagged with the given tags
+    pub fn track(&self, start: DateTime<Utc>, end: DateTime<Utc>, tags: &[String]) -> Result<()> {
+        let mut args = vec![
+            "track".to_string(),
+            start.format("%Y%m%dT%H%M%SZ").to_string(),
+            "-".to_string(),
+            end.format("%Y%m%dT%H%M%SZ").to_string(),
+        ];
+        args.extend(tags.iter().cloned());
+        args.push(":quiet".to_string());
+        run(&self.bin, &args)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::fs;
+
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::integrations::test_support::{spawn_lock, stub_binary, temp_dir};
+
+    #[test]
+    fn tracks_an_interval_through_a_stub_binary() {
+        let _lock = spawn_lock();
+        let dir = temp_dir("timewarrior");
+        let script = format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > \"{}\"\n", dir.join("args").display());
+        let bin = stub_binary(&dir, "timew", &script);
+
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 9, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 5, 1, 9, 25, 0).unwrap();
+        Timewarrior::new(&bin)
+            .track(start, end, &["Write chapter".to_string(), "book".to_string()])
+            .unwrap();
+
+        let args = fs::read_to_string(dir.join("args")).unwrap();
+        assert_eq!(
+            args.lines().collect::<Vec<_>>(),
+            ["track", "20240501T090000Z", "-", "20240501T092500Z", "Write chapter", "book", ":quiet"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failing_binary_is_an_error() {
+        let _lock = spawn_lock();
+        let now = Utc::now();
+        assert!(Timewarrior::new("false").track(now, now, &[]).is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! todo.txt task queue with pomodoro estimates
+//!
+//! Estimates are stored with a `pomo:N` extension and completed pomodoros with
+//! `pomo_done:N`, so the file stays readable by other todo.txt tools. Changes edit
+//! only the line of the task they apply to, leaving every other byte of the file as
+//! it was.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDate};
+use color_eyre::eyre::{Context, Result};
+
+/// Extension key holding the estimated pomodoro count
+const ESTIMATE_KEY: &str = "pomo";
+/// Extension key holding the completed pomodoro count
+const ACTUAL_KEY: &str = "pomo_done";
+
+/// A single line of a todo.txt file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoItem {
+    /// Whether the task is marked done with a leading `x`
+    pub done: bool,
+    /// Priority letter, e.g. `A` for `(A)`
+    pub priority: Option<char>,
+    /// Date the task was completed
+    pub completion_date: Option<NaiveDate>,
+    /// Date the task was created
+    pub creation_date: Option<NaiveDate>,
+    /// Description including projects, contexts and other extensions
+    pub text: String,
+    /// Estimated number of pomodoros
+    pub estimate: Option<u32>,
+    /// Pomodoros completed so far
+    pub actual: u32,
+}
+
+impl TodoItem {
+    /// Create a new open task created today
+    pub fn new(text: String, estimate: Option<u32>) -> Self {
+        Self {
+            done: false,
+            priority: None,
+            completion_date: None,
+            creation_date: Some(Local::now().date_naive()),
+            text,
+            estimate,
+            actual: 0,
+        }
+    }
+
+    /// Parse a line in todo.txt format
+    pub fn parse(line: &str) -> Self {
+        let mut rest = line.trim();
+        let mut item = Self {
+            done: false,
+            priority: None,
+            completion_date: None,
+            creation_date: None,
+            text: String::new(),
+            estimate: None,
+            actual: 0,
+        };
+
+        if let Some(after) = rest.strip_prefix("x ") {
+            item.done = true;
+            rest = after.trim_start();
+        }
+
+        if let [b'(', p, b')', b' ', ..] = rest.as_bytes()
+            && p.is_ascii_uppercase()
+        {
+            item.priority = Some(*p as char);
+            rest = rest[4..].trim_start();
+        }
+
+        if let Some((date, after)) = take_date(rest) {
+            // A done task may carry a completion date followed by a creation date
+            if item.done {
+                item.completion_date = Some(date);
+                rest = after;
+                if let Some((created, after)) = take_date(rest) {
+                    item.creation_date = Some(created);
+                    rest = after;
+                }
+            } else {
+                item.creation_date = Some(date);
+                rest = after;
+            }
+        }
+
+        let mut words = Vec::new();
+        for word in rest.split_whitespace() {
+            match word.split_once(':') {
+                Some((ESTIMATE_KEY, value)) if value.parse::<u32>().is_ok() => {
+                    item.estimate = value.parse().ok();
+                }
+                Some((ACTUAL_KEY, value)) if value.parse::<u32>().is_ok() => {
+                    item.actual = value.parse().unwrap_or(0);
+                }
+                Some(("pri", value)) if item.done && value.len() == 1 => {
+                    item.priority = value.chars().next();
+                }
+                _ => words.push(word),
+            }
+        }
+        item.text = words.join(" ");
+        item
+    }
+
+    /// Format the item as a todo.txt line
+    pub fn format(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        if self.done {
+            parts.push("x".to_string());
+            if let Some(date) = self.completion_date {
+                parts.push(date.to_string());
+            }
+        } else if let Some(priority) = self.priority {
+            parts.push(format!("({})", priority));
+        }
+        if let Some(date) = self.creation_date {
+            parts.push(date.to_string());
+        }
+        parts.push(self.text.clone());
+        // Done tasks keep their priority as an extension, per todo.txt convention
+        if let (true, Some(priority)) = (self.done, self.priority) {
+            parts.push(format!("pri:{}", priority));
+        }
+        if let Some(estimate) = self.estimate {
+            parts.push(format!("{}:{}", ESTIMATE_KEY, estimate));
+        }
+        if self.actual > 0 {
+            parts.push(format!("{}:{}", ACTUAL_KEY, self.actual));
+        }
+
+        parts.join(" ")
+    }
+
+    /// Progress label such as `2/4` or `2/?`
+    pub fn progress(&self) -> String {
+        match self.estimate {
+            Some(estimate) => format!("{}/{}", self.actual, estimate),
+            None => format!("{}/?", self.actual),
+        }
+    }
+}
+
+/// A todo.txt file used as the pomodoro task queue
+#[derive(Debug, Clone)]
+pub struct TodoTxt {
+    path: PathBuf,
+}
+
+impl TodoTxt {
+    /// Use the todo.txt file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the todo.txt file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every item, in file order
+    pub fn load(&self) -> Result<Vec<TodoItem>> {
+        Ok(self
+            .read()?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(TodoItem::parse)
+            .collect())
+    }
+
+    /// Add a task at the end of the file
+    pub fn append(&self, item: &TodoItem) -> Result<()> {
+        let mut content = self.read()?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&item.format());
+        content.push('\n');
+        self.write(&content)
+    }
+
+    /// Set the estimate of the item at `index` in [`TodoTxt::load`] order
+    pub fn set_estimate(&self, index: usize, estimate: u32) -> Result<Option<TodoItem>> {
+        self.edit(index, |line| set_extension(line, ESTIMATE_KEY, estimate))
+    }
+
+    /// Mark the item at `index` in [`TodoTxt::load`] order done today
+    pub fn complete_at(&self, index: usize) -> Result<Option<TodoItem>> {
+        self.edit(index, |line| mark_done(line, Local::now().date_naive()))
+    }
+
+    /// The first open task, which is the next one to work on
+    pub fn next_task(&self) -> Result<Option<TodoItem>> {
+        Ok(self.load()?.into_iter().find(|item| !item.done))
+    }
+
+    /// Find the open task with exactly this description
+    pub fn find_open(&self, text: &str) -> Result<Option<TodoItem>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .find(|item| !item.done && item.text == text.trim()))
+    }
+
+    /// Count a completed pomodoro against the open task with this description
+    pub fn record_pomodoro(&self, text: &str) -> Result<Option<TodoItem>> {
+        let Some((index, item)) = self.find_open_index(text)? else {
+            return Ok(None);
+        };
+        self.edit(index, |line| set_extension(line, ACTUAL_KEY, item.actual + 1))
+    }
+
+    /// Mark the open task with this description as done
+    pub fn complete(&self, text: &str) -> Result<Option<TodoItem>> {
+        let Some((index, _)) = self.find_open_index(text)? else {
+            return Ok(None);
+        };
+        self.complete_at(index)
+    }
+
+    /// Position and contents of the first open task with this description
+    fn find_open_index(&self, text: &str) -> Result<Option<(usize, TodoItem)>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .enumerate()
+            .find(|(_, item)| !item.done && item.text == text.trim()))
+    }
+
+    /// Rewrite the line of the item at `index`, returning the item it now holds
+    fn edit(&self, index: usize, change: impl FnOnce(&str) -> String) -> Result<Option<TodoItem>> {
+        let content = self.read()?;
+        let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let Some(line) = lines
+            .iter_mut()
+            .filter(|line| !line.trim().is_empty())
+            .nth(index)
+        else {
+            return Ok(None);
+        };
+
+        // Keep the line ending, whether `\n`, `\r\n` or none on the last line
+        let body = line.trim_end_matches(['\r', '\n']);
+        let changed = change(body) + &line[body.len()..];
+        let item = TodoItem::parse(&changed);
+        *line = &changed;
+        self.write(&lines.concat())?;
+        Ok(Some(item))
+    }
+
+    fn read(&self) -> Result<String> {
+        if !self.path.exists() {
+            return Ok(String::new());
+        }
+        fs::read_to_string(&self.path).wrap_err_with(|| format!("Failed to read {}", self.path.display()))
+    }
+
+    fn write(&self, content: &str) -> Result<()> {
+        fs::write(&self.path, content).wrap_err_with(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Mark a line done on `date`, turning a leading `(A)` priority into `pri:A`
+fn mark_done(line: &str, date: NaiveDate) -> String {
+    let task = line.trim_start();
+    let indent = &line[..line.len() - task.len()];
+    match task.as_bytes() {
+        [b'(', p, b')', b' ', ..] if p.is_ascii_uppercase() => {
+            format!("{}x {} {} pri:{}", indent, date, task[4..].trim_start(), *p as char)
+        }
+        _ => format!("{}x {} {}", indent, date, task),
+    }
+}
+
+/// Set a `key:N` extension in place, or add it at the end of the line
+fn set_extension(line: &str, key: &str, value: u32) -> String {
+    let mut offset = 0;
+    for word in line.split_inclusive(char::is_whitespace) {
+        let token = word.trim_end();
+        if let Some((word_key, number)) = token.split_once(':')
+            && word_key == key
+            && number.parse::<u32>().is_ok()
+        {
+            let start = offset + key.len() + 1;
+            return format!("{}{}{}", &line[..start], value, &line[start + number.len()..]);
+        }
+        offset += word.len();
+    }
+    format!("{} {}:{}", line.trim_end(), key, value)
+}
+
+/// Split a leading `YYYY-MM-DD ` date off a string
+fn take_date(input: &str) -> Option<(NaiveDate, &str)> {
+    let (word, rest) = input.split_once(' ').unwrap_or((input, ""));
+    NaiveDate::parse_from_str(word, "%Y-%m-%d")
+        .ok()
+        .map(|date| (date, rest.trim_start()))
+}
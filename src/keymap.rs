@@ -0,0 +1,466 @@
+//! Key bindings from key presses to the actions they stand for, per view
+//!
+//! Defaults are built in and can be changed in `keys.toml` in the config directory:
+//! each table names a context and maps command names to one key or a list of keys.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use color_eyre::eyre::{bail, eyre, Context as _, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::action::Action;
+use crate::components::hints::Hint;
+
+/// A key press, ignoring Shift (which is already part of the character)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl Key {
+    /// A plain key without modifiers
+    pub const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    /// The character typed, for a character key without Ctrl or Alt
+    pub fn char(self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if !self.ctrl && !self.alt => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Whether this is Ctrl-C, which always quits and cannot be rebound
+    pub fn is_ctrl_c(self) -> bool {
+        self.ctrl && matches!(self.code, KeyCode::Char('c' | 'C'))
+    }
+
+    /// What the key does while typing text, where bindings do not apply
+    pub fn text_action(self) -> Action {
+        match self.code {
+            KeyCode::Char('w') if self.ctrl => Action::DeleteWord,
+            KeyCode::Char('u') if self.ctrl => Action::DeleteToStart,
+            KeyCode::Char('a') if self.ctrl => Action::Home,
+            KeyCode::Char('e') if self.ctrl => Action::End,
+            KeyCode::Enter => Action::Confirm,
+            KeyCode::Esc => Action::Cancel,
+            KeyCode::Backspace if self.alt => Action::DeleteWord,
+            KeyCode::Backspace => Action::Backspace,
+            KeyCode::Delete => Action::Delete,
+            KeyCode::Tab => Action::NextTab,
+            KeyCode::BackTab => Action::PrevTab,
+            KeyCode::Right => Action::CursorRight,
+            KeyCode::Left => Action::CursorLeft,
+            KeyCode::Home => Action::Home,
+            KeyCode::End => Action::End,
+            KeyCode::Up => Action::ScrollUp,
+            KeyCode::Down => Action::ScrollDown,
+            KeyCode::PageUp => Action::PageUp,
+            KeyCode::PageDown => Action::PageDown,
+            KeyCode::Char(c) if !self.ctrl && !self.alt => Action::Input(c),
+            _ => Action::None,
+        }
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(KeyModifiers::ALT),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Parse a key such as `q`, `space`, `pgup`, `f5` or `ctrl-r`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut key = Key::new(KeyCode::Null);
+        let mut rest = text;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            if rest.chars().count() > 1 && (lower.starts_with("ctrl-") || lower.starts_with("ctrl+")) {
+                key.ctrl = true;
+                rest = &rest[5..];
+            } else if rest.chars().count() > 1 && (lower.starts_with("alt-") || lower.starts_with("alt+")) {
+                key.alt = true;
+                rest = &rest[4..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = rest.chars();
+        key.code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" | "shift-tab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", text)),
+                },
+            },
+        };
+        Ok(key)
+    }
+}
+
+impl fmt::Display for Key {
+    /// Label shown in hint bars, e.g. `Space`, `PgUp` or `Ctrl-r`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl-")?;
+        }
+        if self.alt {
+            write!(f, "Alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Where a binding applies
+///
+/// Global bindings work in every view. Timer bindings also work in every view, but a
+/// view's own bindings take precedence over them while it is showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Context {
+    Global,
+    Timer,
+    Plan,
+    History,
+    Stats,
+}
+
+impl Context {
+    pub const ALL: [Context; 5] = [
+        Context::Global,
+        Context::Timer,
+        Context::Plan,
+        Context::History,
+        Context::Stats,
+    ];
+
+    /// Table name in `keys.toml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::Timer => "timer",
+            Context::Plan => "plan",
+            Context::History => "history",
+            Context::Stats => "stats",
+        }
+    }
+}
+
+/// A bindable command, with its default keys
+#[derive(Debug)]
+pub struct Command {
+    pub context: Context,
+    /// Name in `keys.toml`
+    pub name: &'static str,
+    pub action: Action,
+    pub description: &'static str,
+    pub keys: &'static [&'static str],
+}
+
+const fn command(
+    context: Context,
+    name: &'static str,
+    action: Action,
+    description: &'static str,
+    keys: &'static [&'static str],
+) -> Command {
+    Command {
+        context,
+        name,
+        action,
+        description,
+        keys,
+    }
+}
+
+/// Every command that can be bound, in the order they are listed
+pub static COMMANDS: &[Command] = &[
+    command(Context::Global, "quit", Action::Quit, "Quit, saving a running session", &["q", "Q"]),
+    command(Context::Global, "next_view", Action::NextTab, "Switch to the next view", &["tab"]),
+    command(Context::Global, "prev_view", Action::PrevTab, "Switch to the previous view", &["backtab"]),
+    command(Context::Global, "left", Action::CursorLeft, "Move left, or to the previous view", &["left"]),
+    command(Context::Global, "right", Action::CursorRight, "Move right, or to the next view", &["right"]),
+    command(Context::Global, "up", Action::ScrollUp, "Move up", &["up"]),
+    command(Context::Global, "down", Action::ScrollDown, "Move down", &["down"]),
+    command(Context::Global, "page_up", Action::PageUp, "Page up, or back in the chart", &["pgup"]),
+    command(Context::Global, "page_down", Action::PageDown, "Page down, or forward in the chart", &["pgdn"]),
+    command(Context::Global, "top", Action::Home, "Move to the first row", &["home"]),
+    command(Context::Global, "bottom", Action::End, "Move to the last row", &["end"]),
+    command(Context::Global, "confirm", Action::Confirm, "Start, open or confirm", &["enter"]),
+    command(Context::Global, "cancel", Action::Cancel, "Close or cancel", &["esc"]),
+    command(Context::Global, "filter", Action::CycleFilter, "Cycle the History and Stats filter", &["f", "F"]),
+    command(Context::Global, "help", Action::ShowHelp, "Show the keys for the current view", &["?"]),
+    command(Context::Global, "palette", Action::OpenPalette, "Run any command by name", &[":"]),
+    command(Context::Timer, "pause", Action::TogglePause, "Pause or resume the work timer", &["space"]),
+    command(Context::Timer, "stop", Action::Stop, "Stop and save as interrupted", &["r", "R"]),
+    command(Context::Timer, "skip", Action::Skip, "Skip or end the break", &["s", "S"]),
+    command(Context::Timer, "break", Action::StartBreak, "Start a break after a work session", &["b", "B"]),
+    command(Context::Timer, "repeat", Action::Repeat, "Go again on the last task", &["."]),
+    command(Context::Timer, "done", Action::Done, "Mark the finished task done", &["d", "D"]),
+    command(Context::Timer, "notes", Action::Notes, "Write notes on the finished session", &["n", "N"]),
+    command(Context::Plan, "add", Action::AddTask, "Add a task", &["a", "A"]),
+    command(Context::Plan, "more", Action::IncreaseEstimate, "Add a pomodoro to the estimate", &["+", "="]),
+    command(Context::Plan, "less", Action::DecreaseEstimate, "Take a pomodoro off the estimate", &["-"]),
+    command(Context::Plan, "toggle_done", Action::ToggleDone, "Mark the task done or not done", &["x", "X"]),
+    command(Context::Plan, "move_up", Action::MoveUp, "Move the task up", &["K"]),
+    command(Context::Plan, "move_down", Action::MoveDown, "Move the task down", &["J"]),
+    command(Context::Plan, "tomorrow", Action::CarryOver, "Move the task to tomorrow", &["t", "T"]),
+    command(Context::Plan, "delete", Action::DeleteSelected, "Delete the task", &["D"]),
+    command(Context::History, "search", Action::Search, "Search sessions", &["/"]),
+    command(Context::History, "sort", Action::Sort, "Sort by the next column", &["o"]),
+    command(Context::History, "reverse", Action::ReverseSort, "Reverse the sort order", &["O"]),
+    command(Context::History, "group", Action::Group, "Group sessions by day", &["g"]),
+    command(Context::History, "jump", Action::JumpToDate, "Jump to a date", &["J"]),
+    command(Context::History, "edit_task", Action::EditTask, "Edit the task of the open session", &["e", "E"]),
+    command(Context::History, "edit_notes", Action::EditNotes, "Edit the notes of the open session", &["n", "N"]),
+    command(Context::History, "delete", Action::DeleteSelected, "Delete the open session", &["D"]),
+    command(Context::Stats, "heatmap", Action::Heatmap, "Toggle the heatmap", &["h"]),
+    command(Context::Stats, "distribution", Action::Distribution, "Toggle focus by hour and weekday", &["d"]),
+    command(Context::Stats, "estimates", Action::Estimates, "Toggle estimation accuracy", &["e"]),
+];
+
+/// One key bound to a command
+#[derive(Debug, Clone)]
+struct Binding {
+    key: Key,
+    command: &'static Command,
+}
+
+/// The active key bindings
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = COMMANDS
+            .iter()
+            .flat_map(|command| {
+                command.keys.iter().map(move |key| Binding {
+                    key: key.parse().expect("default keys parse"),
+                    command,
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+/// Keys for one command in `keys.toml`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keymap {
+    /// Load the defaults, changed by the file at `path` if it exists
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut keymap = Self::default();
+        let Some(path) = path.filter(|path| path.exists()) else {
+            return Ok(keymap);
+        };
+
+        let contents =
+            fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        keymap
+            .apply(&contents)
+            .and_then(|()| keymap.check_conflicts())
+            .wrap_err_with(|| format!("Invalid key bindings in {}", path.display()))?;
+        Ok(keymap)
+    }
+
+    /// Rebind the commands listed in a `keys.toml` document, replacing their default keys
+    fn apply(&mut self, contents: &str) -> Result<()> {
+        let tables: BTreeMap<String, BTreeMap<String, KeyList>> = toml::from_str(contents)?;
+        for (table, entries) in tables {
+            let context = Context::ALL
+                .into_iter()
+                .find(|context| context.name() == table)
+                .ok_or_else(|| eyre!("unknown section [{}], expected one of {}", table, context_names()))?;
+            for (name, keys) in entries {
+                let command = COMMANDS
+                    .iter()
+                    .find(|command| command.context == context && command.name == name)
+                    .ok_or_else(|| eyre!("unknown command '{}' in [{}]", name, table))?;
+                let keys = match keys {
+                    KeyList::One(key) => vec![key],
+                    KeyList::Many(keys) => keys,
+                };
+
+                self.bindings.retain(|binding| !std::ptr::eq(binding.command, command));
+                for key in keys {
+                    let key: Key = key.parse().map_err(|err| eyre!("{} for {}.{}", err, table, name))?;
+                    if key.is_ctrl_c() {
+                        bail!("ctrl-c always quits and cannot be bound, for {}.{}", table, name);
+                    }
+                    self.bindings.push(Binding { key, command });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fail if a key is bound to two commands that could both apply
+    ///
+    /// A key may be bound once per context. A global key may not be bound again in
+    /// another context; a view may reuse a timer key, and takes precedence.
+    pub fn check_conflicts(&self) -> Result<()> {
+        let mut conflicts = Vec::new();
+        for (i, first) in self.bindings.iter().enumerate() {
+            for second in &self.bindings[i + 1..] {
+                let (a, b) = (first.command, second.command);
+                if first.key == second.key
+                    && !std::ptr::eq(a, b)
+                    && (a.context == b.context || a.context == Context::Global || b.context == Context::Global)
+                {
+                    conflicts.push(format!(
+                        "'{}' is bound to both {}.{} and {}.{}",
+                        first.key,
+                        a.context.name(),
+                        a.name,
+                        b.context.name(),
+                        b.name
+                    ));
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            bail!("conflicting key bindings:\n  {}", conflicts.join("\n  "));
+        }
+        Ok(())
+    }
+
+    /// Action bound to `key` in a view, falling back to timer then global bindings
+    pub fn action(&self, context: Context, key: Key) -> Option<Action> {
+        [context, Context::Timer, Context::Global].into_iter().find_map(|context| {
+            self.bindings
+                .iter()
+                .find(|binding| binding.key == key && binding.command.context == context)
+                .map(|binding| binding.command.action.clone())
+        })
+    }
+
+    /// Keys bound to `action` in a context, or globally
+    pub fn keys(&self, context: Context, action: &Action) -> Vec<Key> {
+        [context, Context::Global]
+            .into_iter()
+            .map(|context| {
+                self.bindings
+                    .iter()
+                    .filter(|binding| binding.command.context == context && binding.command.action == *action)
+                    .map(|binding| binding.key)
+                    .collect::<Vec<_>>()
+            })
+            .find(|keys| !keys.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Keys bound to a command
+    pub fn command_keys(&self, command: &Command) -> Vec<Key> {
+        self.bindings
+            .iter()
+            .filter(|binding| std::ptr::eq(binding.command, command))
+            .map(|binding| binding.key)
+            .collect()
+    }
+
+    /// Hint bar entries: the first key of each action joined by `/`, then the label
+    ///
+    /// Clicking an entry runs its first bound action. Entries whose actions are all
+    /// unbound are left out.
+    pub fn hints<'a>(&self, context: Context, entries: &[(&[Action], &'a str)]) -> Vec<Hint<'a>> {
+        entries
+            .iter()
+            .filter_map(|(actions, label)| {
+                let bound: Vec<(&Action, Key)> = actions
+                    .iter()
+                    .filter_map(|action| self.keys(context, action).first().map(|key| (action, *key)))
+                    .collect();
+                let (action, _) = bound.first()?;
+                let keys: Vec<String> = bound.iter().map(|(_, key)| key.to_string()).collect();
+                Some(Hint::new(keys.join("/"), label, (*action).clone()))
+            })
+            .collect()
+    }
+
+    /// Plain-text listing of every command and its keys, by context
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        for context in Context::ALL {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{}]\n", context.name()));
+            for command in COMMANDS.iter().filter(|command| command.context == context) {
+                let keys: Vec<String> = self.command_keys(command).iter().map(Key::to_string).collect();
+                let keys = if keys.is_empty() { "-".to_string() } else { keys.join(" ") };
+                out.push_str(&format!("  {:<14} {:<16} {}\n", command.name, keys, command.description));
+            }
+        }
+        out.push_str("\nctrl-c always quits, including while typing\n");
+        out
+    }
+}
+
+fn context_names() -> String {
+    Context::ALL.map(Context::name).join(", ")
+}
@@ -1,22 +1,64 @@
 mod action;
 mod app;
+mod chart;
 mod components;
+mod config;
+mod distribution;
+mod editor;
+mod estimate;
+mod export;
+mod goal;
+mod heatmap;
+mod ical;
+mod import;
+mod integrations;
+mod keymap;
+mod mouse;
+mod output;
+mod palette;
+mod parse;
+mod plan;
+mod report;
 mod session;
 mod storage;
+mod suggest;
+mod text;
 mod theme;
 mod tui;
 
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
 use std::time::Duration;
 
-use clap::{Parser, Subcommand};
-use color_eyre::eyre::Result;
+use clap::{Args, Parser, Subcommand};
+use color_eyre::eyre::{bail, Context, Result};
+use crossterm::style::Stylize;
 use ratatui::layout::{Constraint, Layout};
 
 use crate::action::Action;
 use crate::app::{App, AppState, View};
-use crate::components::session_list::{display_sessions, SessionFilter, SessionStats};
-use crate::components::{HistoryWidget, StatsWidget, TabsWidget, TaskInputWidget, TimerWidget};
+use crate::components::session_list::{SessionFilter, SessionQuery, SessionStats, StatsComparison};
+use crate::components::{
+    HelpWidget, HistoryWidget, PaletteWidget, PlanWidget, RetroWidget, StatsWidget, TabsWidget, TaskInputWidget,
+    TimerWidget,
+};
+use crate::config::Config;
+use crate::distribution::Distribution;
+use crate::estimate::EstimateReport;
+use crate::export::{Column, ExportFormat, ExportOptions, Timestamps, DEFAULT_COLUMNS};
+use crate::goal::DEFAULT_POMODORO_SECS;
+use crate::heatmap::Heatmap;
+use crate::import::{Field, ImportFormat};
+use crate::integrations::{Integrations, Taskwarrior, Timewarrior, TodoItem, TodoTxt};
+use crate::keymap::Keymap;
+use crate::output::{
+    display_comparison, display_grouped_sessions, display_sessions, display_stats, OutputFormat, StatsRecord,
+    StatsReport,
+};
+use crate::report::{Report, ReportFormat, ReportGrouping, ReportPeriod};
+use crate::session::Session;
 use crate::storage::Storage;
+use crate::theme::{ColorSupport, Theme};
 use crate::tui::Tui;
 
 /// A Pomodoro timer TUI application
@@ -27,6 +69,13 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
+    #[command(flatten)]
+    timer: TimerArgs,
+}
+
+/// Options for the timer TUI
+#[derive(Args, Debug)]
+struct TimerArgs {
     /// Duration of pomodoro in minutes
     #[arg(short, long, default_value = "25")]
     duration: u32,
@@ -42,52 +91,258 @@ struct Cli {
     /// Number of work sessions before a long break
     #[arg(short = 'n', long, default_value = "4")]
     long_break_interval: u32,
+
+    /// Pick tasks from Taskwarrior and annotate them when a pomodoro completes
+    #[arg(long)]
+    taskwarrior: bool,
+
+    /// Record completed pomodoros as Timewarrior intervals
+    #[arg(long)]
+    timewarrior: bool,
+
+    /// Path to the Taskwarrior binary
+    #[arg(long, value_name = "PATH", default_value = "task")]
+    task_bin: PathBuf,
+
+    /// Path to the Timewarrior binary
+    #[arg(long, value_name = "PATH", default_value = "timew")]
+    timew_bin: PathBuf,
+
+    /// Take tasks from this todo.txt queue (defaults to $TODO_FILE when set)
+    #[arg(long, value_name = "PATH")]
+    todo: Option<PathBuf>,
+
+    /// Don't ask for a quick retrospective when a pomodoro completes
+    #[arg(long)]
+    no_retro: bool,
+
+    /// Use the timer lengths of a profile from config.toml
+    #[arg(short, long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Leave the mouse to the terminal, so text can be selected and copied
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// Color theme: dark, light, solarized, high-contrast or a theme file (overrides the config)
+    #[arg(long, value_name = "NAME|PATH")]
+    theme: Option<String>,
+}
+
+impl TimerArgs {
+    /// Integrations enabled by the flags
+    fn integrations(&self) -> Integrations {
+        Integrations {
+            taskwarrior: self.taskwarrior.then(|| Taskwarrior::new(&self.task_bin)),
+            timewarrior: self.timewarrior.then(|| Timewarrior::new(&self.timew_bin)),
+            todo_txt: todo_file(self.todo.clone()).map(TodoTxt::new),
+        }
+    }
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Start a new pomodoro timer (default)
     Start {
-        /// Duration in minutes
-        #[arg(short, long, default_value = "25")]
-        duration: u32,
-
-        /// Short break duration in minutes
-        #[arg(short = 's', long, default_value = "5")]
-        short_break: u32,
-
-        /// Long break duration in minutes
-        #[arg(short = 'l', long, default_value = "15")]
-        long_break: u32,
-
-        /// Number of work sessions before a long break
-        #[arg(short = 'n', long, default_value = "4")]
-        long_break_interval: u32,
+        #[command(flatten)]
+        timer: TimerArgs,
     },
     /// List past sessions
     List {
-        /// Show only today's sessions
-        #[arg(long, conflicts_with = "all")]
-        today: bool,
-        /// Show this week's sessions
-        #[arg(long, conflicts_with = "all")]
-        week: bool,
-        /// Show all sessions
-        #[arg(long)]
-        all: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Group sessions under a heading per day or week, with subtotals
+        #[arg(long, value_enum, value_name = "PERIOD")]
+        group_by: Option<ReportPeriod>,
+        /// Print a table, or JSON, YAML or CSV for scripts
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table, conflicts_with = "group_by")]
+        output: OutputFormat,
     },
     /// Show focus time statistics
     Stats {
-        /// Show only today's stats
-        #[arg(long, conflicts_with = "all")]
-        today: bool,
-        /// Show this week's stats
-        #[arg(long, conflicts_with = "all")]
-        week: bool,
-        /// Show all-time stats
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Show a calendar heatmap of daily focus over the past year instead
+        ///
+        /// The year ends on `--to` or today; only `--to` and `--tag` apply.
+        #[arg(long, conflicts_with_all = ["today", "week", "month", "year", "all", "from"])]
+        heatmap: bool,
+        /// Color theme of the heatmap (overrides the config)
+        #[arg(long, value_name = "NAME|PATH", requires = "heatmap")]
+        theme: Option<String>,
+        /// Show focus time and completion rate by hour of day and weekday instead
+        #[arg(long, conflicts_with = "heatmap")]
+        distribution: bool,
+        /// Compare with the period before, e.g. this week against last week
+        #[arg(long, conflicts_with_all = ["heatmap", "distribution"])]
+        compare: bool,
+        /// Show how finished tasks compared with their pomodoro estimates instead
+        #[arg(long, conflicts_with_all = ["heatmap", "distribution", "compare"])]
+        estimates: bool,
+        /// Print a table, or JSON, YAML or CSV for scripts
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Manually log a session done away from the timer
+    Log {
+        /// Description of the task worked on
+        #[arg(short, long)]
+        task: String,
+        /// Start time as HH:MM (today) or YYYY-MM-DD HH:MM, in local time
+        #[arg(long, value_parser = parse::parse_start_time)]
+        at: chrono::DateTime<chrono::Utc>,
+        /// How long the session lasted, e.g. 25m, 1h30m, 90s
+        #[arg(short, long, default_value = "25m", value_parser = parse::parse_duration)]
+        duration: u32,
+        /// Mark the session as interrupted rather than completed
         #[arg(long)]
-        all: bool,
+        interrupted: bool,
+    },
+    /// Export sessions as CSV, JSON, JSON Lines or iCalendar (all sessions unless filtered)
+    Export {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Comma-separated columns to include, in order
+        #[arg(short, long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+        /// Timezone for exported timestamps
+        #[arg(long, value_enum, default_value_t = Timestamps::Local)]
+        timestamps: Timestamps,
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<PathBuf>,
+        /// Keep the output file updated as sessions are recorded (e.g. for calendar subscriptions)
+        #[arg(short, long, requires = "out")]
+        watch: bool,
+    },
+    /// Import sessions from a CSV file or another tracker's export
+    Import {
+        /// File to import
+        file: PathBuf,
+        /// Format of the file
+        #[arg(short, long, value_enum, default_value_t = ImportFormat::Csv)]
+        format: ImportFormat,
+        /// Read a field from a differently named CSV column, e.g. task=Description (repeatable)
+        #[arg(short, long = "map", value_name = "FIELD=COLUMN", value_parser = import::parse_mapping)]
+        mappings: Vec<(Field, String)>,
+        /// Show what would be imported without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Plan a queue of tasks with pomodoro estimates in a todo.txt file
+    Queue {
+        /// Path to the todo.txt file (defaults to $TODO_FILE)
+        #[arg(long, value_name = "PATH")]
+        todo: Option<PathBuf>,
+
+        #[command(subcommand)]
+        action: Option<QueueCommand>,
+    },
+    /// Render a timesheet report of focus time
+    Report {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+        /// Break the report down per day or per week
+        #[arg(long, value_enum, default_value_t = ReportPeriod::Day)]
+        by: ReportPeriod,
+        /// Group sessions within each period by task or tag
+        #[arg(short, long, value_enum, default_value_t = ReportGrouping::Task)]
+        group: ReportGrouping,
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// Show the active key bindings and check keys.toml for conflicts
+    Keys,
+}
+
+#[derive(Subcommand, Debug)]
+enum QueueCommand {
+    /// Show open tasks in queue order (default)
+    List,
+    /// Add a task to the end of the queue
+    Add {
+        /// Task description (may include +project and @context)
+        text: String,
+        /// Estimated number of pomodoros
+        #[arg(short, long)]
+        estimate: Option<u32>,
     },
+    /// Set the pomodoro estimate of a queued task
+    Estimate {
+        /// Position in the queue, as shown by `queue list`
+        position: usize,
+        /// Estimated number of pomodoros
+        pomodoros: u32,
+    },
+    /// Mark a queued task as done
+    Done {
+        /// Position in the queue, as shown by `queue list`
+        position: usize,
+    },
+}
+
+/// Flags selecting which sessions a command operates on
+#[derive(Args, Debug)]
+struct FilterArgs {
+    /// Only include today's sessions
+    #[arg(long, conflicts_with_all = ["week", "month", "year", "all"])]
+    today: bool,
+    /// Only include the past week's sessions
+    #[arg(long, conflicts_with_all = ["month", "year", "all"])]
+    week: bool,
+    /// Only include sessions since the first of this month
+    #[arg(long, conflicts_with_all = ["year", "all"])]
+    month: bool,
+    /// Only include sessions since the first of January
+    #[arg(long, conflicts_with = "all")]
+    year: bool,
+    /// Include all sessions
+    #[arg(long)]
+    all: bool,
+    /// Only include sessions on or after this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse::parse_date)]
+    from: Option<chrono::NaiveDate>,
+    /// Only include sessions on or before this date (YYYY-MM-DD)
+    #[arg(long, value_parser = parse::parse_date)]
+    to: Option<chrono::NaiveDate>,
+    /// Only include sessions tagged with #TAG (repeatable)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+}
+
+impl FilterArgs {
+    /// Build a query from the flags, falling back to `default` when no window is given
+    ///
+    /// An explicit `--from`/`--to` range widens the default window to all time.
+    fn query(&self, default: SessionFilter) -> SessionQuery {
+        let filter = if self.today {
+            SessionFilter::Today
+        } else if self.week {
+            SessionFilter::Week
+        } else if self.month {
+            SessionFilter::Month
+        } else if self.year {
+            SessionFilter::Year
+        } else if self.all || self.from.is_some() || self.to.is_some() {
+            SessionFilter::All
+        } else {
+            default
+        };
+
+        SessionQuery {
+            filter,
+            from: self.from,
+            to: self.to,
+            tags: self.tags.clone(),
+        }
+    }
 }
 
 fn main() -> Result<()> {
@@ -96,68 +351,118 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Some(Commands::Start {
-            duration,
-            short_break,
-            long_break,
-            long_break_interval,
-        }) => run_timer(duration, short_break, long_break, long_break_interval),
-        Some(Commands::List { today, week, all }) => {
-            let filter = if today {
-                SessionFilter::Today
-            } else if all {
-                SessionFilter::All
-            } else if week {
-                SessionFilter::Week
+        Some(Commands::Start { timer }) => run_timer(&timer),
+        Some(Commands::List {
+            filter,
+            group_by,
+            output,
+        }) => list_sessions(filter.query(SessionFilter::Week), group_by, output),
+        Some(Commands::Stats {
+            filter,
+            heatmap,
+            theme,
+            distribution,
+            compare,
+            estimates,
+            output,
+        }) => {
+            if output != OutputFormat::Table && (heatmap || distribution || estimates) {
+                bail!("--output only applies to the summary statistics, not --heatmap, --distribution or --estimates");
+            }
+            if heatmap {
+                show_heatmap(&filter, theme.as_deref())
+            } else if estimates {
+                show_estimates(filter.query(SessionFilter::All))
+            } else if distribution {
+                show_distribution(filter.query(SessionFilter::Week))
             } else {
-                // Default to week if no flag specified
-                SessionFilter::Week
-            };
-            list_sessions(filter)
+                show_stats(filter.query(SessionFilter::Week), compare, output, &Config::load()?)
+            }
         }
-        Some(Commands::Stats { today, week, all }) => {
-            let filter = if today {
-                SessionFilter::Today
-            } else if all {
-                SessionFilter::All
-            } else if week {
-                SessionFilter::Week
-            } else {
-                SessionFilter::Week
+        Some(Commands::Log {
+            task,
+            at,
+            duration,
+            interrupted,
+        }) => log_session(task, at, duration, !interrupted),
+        Some(Commands::Export {
+            filter,
+            format,
+            columns,
+            timestamps,
+            out,
+            watch,
+        }) => {
+            let options = ExportOptions {
+                format,
+                columns: if columns.is_empty() {
+                    DEFAULT_COLUMNS.to_vec()
+                } else {
+                    columns
+                },
+                timestamps,
             };
-            show_stats(filter)
+            export(filter.query(SessionFilter::All), &options, out, watch)
         }
-        None => run_timer(
-            cli.duration,
-            cli.short_break,
-            cli.long_break,
-            cli.long_break_interval,
-        ),
+        Some(Commands::Import {
+            file,
+            format,
+            mappings,
+            dry_run,
+        }) => import_sessions(&file, format, &mappings, dry_run),
+        Some(Commands::Queue { todo, action }) => queue(todo, action.unwrap_or(QueueCommand::List)),
+        Some(Commands::Report {
+            filter,
+            format,
+            by,
+            group,
+            out,
+        }) => report(filter.query(SessionFilter::Week), format, by, group, out),
+        Some(Commands::Keys) => show_keys(),
+        None => run_timer(&cli.timer),
     }
 }
 
-/// Run the timer TUI
-fn run_timer(
-    duration_minutes: u32,
-    short_break_minutes: u32,
-    long_break_minutes: u32,
-    long_break_interval: u32,
-) -> Result<()> {
-    let mut tui = Tui::new()?;
-    tui.enter()?;
+/// Install the theme named by `--theme`, else by the config, else the dark preset
+fn install_theme(name: Option<&str>, config: &Config) -> Result<()> {
+    let name = name.or(config.theme.as_deref()).unwrap_or("dark");
+    Theme::load(name, Config::themes_dir().as_deref())?
+        .adapt(ColorSupport::detect())
+        .install();
+    Ok(())
+}
 
+/// Run the timer TUI
+fn run_timer(args: &TimerArgs) -> Result<()> {
+    let config = Config::load()?;
+    install_theme(args.theme.as_deref(), &config)?;
+    let keymap = Keymap::load(Config::keys_path().as_deref())?;
     let mut app = App::new(
-        duration_minutes,
-        short_break_minutes,
-        long_break_minutes,
-        long_break_interval,
-    )?;
+        args.duration,
+        args.short_break,
+        args.long_break,
+        args.long_break_interval,
+    )?
+    .with_integrations(args.integrations())
+    .with_retro_prompt(!args.no_retro)
+    .with_goal(config.goal.clone())
+    .with_profiles(config.profiles.clone())
+    .with_keymap(keymap);
+    if let Some(profile) = &args.profile {
+        app.apply_profile(profile)?;
+        app.notice = None;
+    }
+
+    let mouse = !args.no_mouse && config.mouse.unwrap_or(true);
+    let mut tui = Tui::new()?.with_mouse(mouse);
+    tui.enter()?;
 
     // Main event loop
     let tick_rate = Duration::from_millis(250);
 
     while !app.should_quit {
-        // Draw the UI
+        // Draw the UI, with widgets marking what can be clicked
+        app.mouse.borrow_mut().clear();
         tui.draw(|frame| {
             let area = frame.area();
 
@@ -170,13 +475,13 @@ fn run_timer(
 
             // Otherwise show tabbed interface
             let main_chunks = Layout::vertical([
-                Constraint::Length(2),  // Tabs bar
+                Constraint::Length(3),  // Tabs bar: title, titles, border
                 Constraint::Min(10),    // Content area
             ])
             .split(area);
 
             // Render tabs bar
-            let tabs = TabsWidget::new(app.current_view);
+            let tabs = TabsWidget::new(&app);
             tabs.render(frame, main_chunks[0]);
 
             // Render content based on current view
@@ -185,6 +490,10 @@ fn run_timer(
                     let widget = TimerWidget::new(&app);
                     widget.render(frame, main_chunks[1]);
                 }
+                View::Plan => {
+                    let widget = PlanWidget::new(&app);
+                    widget.render(frame, main_chunks[1]);
+                }
                 View::History => {
                     let widget = HistoryWidget::new(&app);
                     widget.render(frame, main_chunks[1]);
@@ -194,6 +503,17 @@ fn run_timer(
                     widget.render(frame, main_chunks[1]);
                 }
             }
+
+            // The retrospective pops up over whichever view is showing
+            if app.retro.is_some() {
+                RetroWidget::new(&app).render(frame, main_chunks[1]);
+            }
+            if app.help {
+                HelpWidget::new(&app).render(frame, main_chunks[1]);
+            }
+            if app.palette.is_some() {
+                PaletteWidget::new(&app).render(frame, main_chunks[1]);
+            }
         })?;
 
         // Handle events
@@ -211,53 +531,336 @@ fn run_timer(
     Ok(())
 }
 
+/// Print every command with its keys, failing on conflicts in keys.toml
+fn show_keys() -> Result<()> {
+    let path = Config::keys_path();
+    let keymap = Keymap::load(path.as_deref())?;
+    if let Some(path) = path {
+        let status = if path.exists() { "" } else { " (not found, using defaults)" };
+        println!("Key bindings from {}{}", path.display(), status);
+        println!();
+    }
+    print!("{}", keymap.render_text());
+    Ok(())
+}
+
 /// List past sessions
-fn list_sessions(filter: SessionFilter) -> Result<()> {
+fn list_sessions(query: SessionQuery, group_by: Option<ReportPeriod>, output: OutputFormat) -> Result<()> {
     let storage = Storage::new()?;
     let sessions = storage.load_sessions()?;
-    display_sessions(&sessions, filter);
+    let filtered = query.apply(&sessions);
+    match (output, group_by) {
+        (OutputFormat::Table, Some(period)) => display_grouped_sessions(&filtered, &query.label(), period),
+        (OutputFormat::Table, None) => display_sessions(&filtered, &query.label()),
+        (format, _) => output::write_sessions(&filtered, format, io::stdout().lock())?,
+    }
     Ok(())
 }
 
 /// Show statistics
-fn show_stats(filter: SessionFilter) -> Result<()> {
+fn show_stats(query: SessionQuery, compare: bool, output: OutputFormat, config: &Config) -> Result<()> {
     let storage = Storage::new()?;
     let sessions = storage.load_sessions()?;
 
-    let now = chrono::Local::now();
-    let today = now.date_naive();
+    let today = chrono::Local::now().date_naive();
+    let previous = match (compare, query.previous(today)) {
+        (false, _) => None,
+        (true, Some(previous)) => Some(previous),
+        (true, None) => bail!("--compare needs a period such as --week, --month or --from"),
+    };
+
+    // Streaks run over every session, whatever the window
+    let streaks = config.goal.streaks(&sessions, today);
+    let stats = SessionStats::from_sessions(query.apply(&sessions)).with_streaks(streaks);
+    let previous = previous.map(|previous| (SessionStats::from_sessions(previous.apply(&sessions)), previous));
+
+    if output != OutputFormat::Table {
+        let report = StatsReport {
+            current: StatsRecord::new(&stats, query.label(), query.range(today)),
+            previous: previous
+                .as_ref()
+                .map(|(stats, previous)| StatsRecord::new(stats, previous.label(), previous.range(today))),
+        };
+        return output::write_stats(&report, output, io::stdout().lock());
+    }
 
-    let filtered: Vec<_> = sessions
-        .iter()
-        .filter(|session| {
-            let session_date = session
-                .started_at
-                .with_timezone(&chrono::Local)
-                .date_naive();
-
-            match filter {
-                SessionFilter::Today => session_date == today,
-                SessionFilter::Week => {
-                    let week_ago = today - chrono::Duration::days(7);
-                    session_date >= week_ago
-                }
-                SessionFilter::All => true,
+    println!("Statistics ({})", query.label());
+    println!();
+    display_stats(&stats);
+
+    if let Some((previous_stats, previous)) = previous {
+        println!();
+        display_comparison(&StatsComparison::new(stats, previous_stats), &query.label(), &previous.label());
+    }
+
+    Ok(())
+}
+
+/// Print focus time and completion rate by hour of day and weekday
+fn show_distribution(query: SessionQuery) -> Result<()> {
+    let storage = Storage::new()?;
+    let sessions = storage.load_sessions()?;
+
+    let distribution = Distribution::from_sessions(query.apply(&sessions));
+    if distribution.is_empty() {
+        println!("No sessions found.");
+        return Ok(());
+    }
+
+    println!("Focus Distribution ({})", query.label());
+    println!();
+    print!("{}", distribution.render_text());
+    Ok(())
+}
+
+/// Print estimated against actual pomodoros for finished planned tasks
+fn show_estimates(query: SessionQuery) -> Result<()> {
+    let storage = Storage::new()?;
+    let sessions = storage.load_sessions()?;
+    let plan = storage.load_plan()?;
+
+    // Without a start the query has no range, but `--to` still bounds it
+    let (from, to) = match query.range(chrono::Local::now().date_naive()) {
+        Some((from, to)) => (Some(from), Some(to)),
+        None => (None, query.to),
+    };
+    let mut report = EstimateReport::new(&plan, &sessions).between(from, to);
+    if !query.tags.is_empty() {
+        let tagged = SessionQuery {
+            filter: SessionFilter::All,
+            from: None,
+            to: None,
+            tags: query.tags.clone(),
+        };
+        report = report.worked_in(&tagged.apply(&sessions));
+    }
+
+    println!("Estimation Accuracy ({})", query.label());
+    println!();
+    print!("{}", report.render_text());
+    Ok(())
+}
+
+/// Print a year-long heatmap of daily focus ending on `--to` or today
+fn show_heatmap(filter: &FilterArgs, theme: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    install_theme(theme, &config)?;
+    let storage = Storage::new()?;
+    let sessions = storage.load_sessions()?;
+
+    // The heatmap always spans a year, so only tags narrow it
+    let query = SessionQuery {
+        filter: SessionFilter::All,
+        from: None,
+        to: None,
+        tags: filter.tags.clone(),
+    };
+    let end = filter.to.unwrap_or_else(|| chrono::Local::now().date_naive());
+    let goals = config.goal.weekly_secs(DEFAULT_POMODORO_SECS);
+    let heatmap = Heatmap::new(query.apply(&sessions), end, goals);
+
+    let color = std::io::stdout().is_terminal() && !Theme::current().monochrome;
+    println!("Focus Heatmap ({} to {})", heatmap.start, heatmap.end);
+    println!();
+    print!(
+        "{}",
+        heatmap.render_text(|shade, level| {
+            if color {
+                shade.with(Theme::current().heatmap[level as usize].into()).to_string()
+            } else {
+                shade.to_string()
             }
         })
-        .cloned()
-        .collect();
+    );
+    Ok(())
+}
+
+/// Record a manually entered session
+fn log_session(
+    task: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    duration_secs: u32,
+    completed: bool,
+) -> Result<()> {
+    let task = task.trim().to_string();
+    if task.is_empty() {
+        bail!("Task description cannot be empty");
+    }
+
+    let session = Session::manual(task, started_at, duration_secs, completed);
+    if session.ended_at() > chrono::Utc::now() {
+        bail!("Cannot log a session that ends in the future");
+    }
+
+    let storage = Storage::new()?;
+    let sessions = storage.load_sessions()?;
+    if let Some(existing) = sessions.iter().find(|existing| existing.overlaps(&session)) {
+        let start = existing.started_at.with_timezone(&chrono::Local);
+        let end = existing.ended_at().with_timezone(&chrono::Local);
+        bail!(
+            "Session overlaps with \"{}\" ({} {}-{})",
+            existing.task,
+            start.format("%Y-%m-%d"),
+            start.format("%H:%M"),
+            end.format("%H:%M"),
+        );
+    }
+
+    let local_start = session.started_at.with_timezone(&chrono::Local);
+    let summary = format!(
+        "Logged \"{}\" at {} ({} min, {})",
+        session.task,
+        local_start.format("%Y-%m-%d %H:%M"),
+        session.duration_secs / 60,
+        if session.completed { "completed" } else { "interrupted" },
+    );
+    storage.save_session(session)?;
+    println!("{}", summary);
+
+    Ok(())
+}
+
+/// Export sessions to stdout or a file
+fn export(query: SessionQuery, options: &ExportOptions, out: Option<PathBuf>, watch: bool) -> Result<()> {
+    let storage = Storage::new()?;
+
+    match out {
+        Some(path) if watch => export::watch(&storage, &query, options, &path)?,
+        Some(path) => {
+            let sessions = storage.load_sessions()?;
+            let filtered = query.apply(&sessions);
+            export::export_to_file(&filtered, options, &path)?;
+            eprintln!("Exported {} sessions to {}", filtered.len(), path.display());
+        }
+        None => {
+            let sessions = storage.load_sessions()?;
+            export::export_sessions(&query.apply(&sessions), options, io::stdout().lock())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Import sessions from another tool, skipping ones already stored
+fn import_sessions(
+    path: &std::path::Path,
+    format: ImportFormat,
+    mappings: &[(Field, String)],
+    dry_run: bool,
+) -> Result<()> {
+    let storage = Storage::new()?;
+    let existing = storage.load_sessions()?;
+
+    let imported = import::read_sessions(path, format, mappings)?;
+    let total = imported.len();
+    let plan = import::plan_import(&existing, imported);
+    let added = plan.new_sessions.len();
+
+    if dry_run {
+        let preview: Vec<&Session> = plan.new_sessions.iter().collect();
+        display_sessions(&preview, "To Import");
+        println!();
+        println!(
+            "Dry run: {} of {} sessions would be imported ({} duplicates skipped)",
+            added, total, plan.duplicates
+        );
+        return Ok(());
+    }
+
+    storage.append_sessions(plan.new_sessions)?;
+    println!(
+        "Imported {} of {} sessions ({} duplicates skipped)",
+        added, total, plan.duplicates
+    );
 
-    let filter_label = match filter {
-        SessionFilter::Today => "Today",
-        SessionFilter::Week => "This Week",
-        SessionFilter::All => "All Time",
+    Ok(())
+}
+
+/// Render a timesheet report to stdout or a file
+fn report(
+    query: SessionQuery,
+    format: ReportFormat,
+    period: ReportPeriod,
+    grouping: ReportGrouping,
+    out: Option<PathBuf>,
+) -> Result<()> {
+    let storage = Storage::new()?;
+    let sessions = storage.load_sessions()?;
+
+    let report = Report::new(query.label(), query.apply(&sessions), period, grouping);
+    let rendered = report.render(format);
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote report to {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+/// Resolve the todo.txt path from a flag, falling back to `$TODO_FILE`
+fn todo_file(explicit: Option<PathBuf>) -> Option<PathBuf> {
+    explicit.or_else(|| std::env::var_os("TODO_FILE").map(PathBuf::from))
+}
+
+/// Manage the todo.txt task queue
+fn queue(todo: Option<PathBuf>, action: QueueCommand) -> Result<()> {
+    let Some(path) = todo_file(todo) else {
+        bail!("No todo.txt file given; pass --todo PATH or set $TODO_FILE");
     };
+    let todo_txt = TodoTxt::new(path);
+    let items = todo_txt.load()?;
 
-    println!("Statistics ({})", filter_label);
-    println!();
+    // Positions refer to open tasks only, in file order
+    let open: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| !item.done)
+        .map(|(i, _)| i)
+        .collect();
+    let index_of = |position: usize| {
+        position
+            .checked_sub(1)
+            .and_then(|i| open.get(i).copied())
+            .ok_or_else(|| color_eyre::eyre::eyre!("No task at position {}", position))
+    };
 
-    let stats = SessionStats::from_sessions(&filtered);
-    stats.display();
+    match action {
+        QueueCommand::List => {
+            if open.is_empty() {
+                println!("Queue is empty ({})", todo_txt.path().display());
+                return Ok(());
+            }
+            println!("{:<4} {:<10} Task", "#", "Pomodoros");
+            println!("{}", "-".repeat(60));
+            for (position, &i) in open.iter().enumerate() {
+                println!("{:<4} {:<10} {}", position + 1, items[i].progress(), items[i].text);
+            }
+            return Ok(());
+        }
+        QueueCommand::Add { text, estimate } => {
+            let text = text.trim().to_string();
+            if text.is_empty() {
+                bail!("Task description cannot be empty");
+            }
+            todo_txt.append(&TodoItem::new(text.clone(), estimate))?;
+            println!("Added \"{}\" at position {}", text, open.len() + 1);
+        }
+        QueueCommand::Estimate { position, pomodoros } => {
+            if let Some(item) = todo_txt.set_estimate(index_of(position)?, pomodoros)? {
+                println!("Estimated \"{}\" at {} pomodoros", item.text, pomodoros);
+            }
+        }
+        QueueCommand::Done { position } => {
+            if let Some(item) = todo_txt.complete_at(index_of(position)?)? {
+                println!("Marked \"{}\" done ({} pomodoros)", item.text, item.progress());
+            }
+        }
+    }
 
     Ok(())
 }
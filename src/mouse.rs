@@ -0,0 +1,51 @@
+//! Screen regions that respond to the mouse, recorded while a frame is drawn
+//!
+//! Widgets only see the app through a shared reference, so they add their regions to
+//! `App::mouse` as they render and the app resolves the next click or scroll against them.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+use crate::action::Action;
+
+/// Clickable and scrollable regions of the last frame
+#[derive(Debug, Default)]
+pub struct MouseTargets {
+    clicks: Vec<(Rect, Action)>,
+    scrolls: Vec<Rect>,
+}
+
+impl MouseTargets {
+    /// Forget the regions of the previous frame
+    pub fn clear(&mut self) {
+        self.clicks.clear();
+        self.scrolls.clear();
+    }
+
+    /// Run `action` when `area` is clicked
+    pub fn click(&mut self, area: Rect, action: Action) {
+        self.clicks.push((area, action));
+    }
+
+    /// Scroll up and down with the wheel over `area`
+    pub fn scroll(&mut self, area: Rect) {
+        self.scrolls.push(area);
+    }
+
+    /// Action for a mouse event, where later regions are drawn over earlier ones
+    pub fn action(&self, event: MouseEvent) -> Action {
+        let position = Position::new(event.column, event.row);
+        let over_scroll = || self.scrolls.iter().any(|area| area.contains(position));
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self
+                .clicks
+                .iter()
+                .rev()
+                .find(|(area, _)| area.contains(position))
+                .map_or(Action::None, |(_, action)| action.clone()),
+            MouseEventKind::ScrollUp if over_scroll() => Action::ScrollUp,
+            MouseEventKind::ScrollDown if over_scroll() => Action::ScrollDown,
+            _ => Action::None,
+        }
+    }
+}
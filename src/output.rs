@@ -0,0 +1,382 @@
+//! Output of `list` and `stats` as human-readable tables or JSON, YAML and CSV for scripts
+//!
+//! The structured formats share one schema, documented in the README: a session is a
+//! [`SessionRecord`] and statistics are a [`StatsReport`].
+
+use std::io::Write;
+
+use chrono::{DateTime, Local, NaiveDate, SecondsFormat, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::components::session_list::{
+    format_days, format_duration, format_duration_long, SessionStats, StatsComparison,
+};
+use crate::report::ReportPeriod;
+use crate::session::Session;
+use crate::text::pad;
+
+/// Output format for `list` and `stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns for reading in a terminal
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    /// One row per record, list values joined by spaces
+    Csv,
+}
+
+/// A session as written by `list --output`
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionRecord {
+    pub id: Uuid,
+    pub task: String,
+    /// `#tags` in the task description, without the `#`
+    pub tags: Vec<String>,
+    /// Local date the session started on
+    pub date: NaiveDate,
+    /// Local start time, RFC 3339 with offset
+    pub start: String,
+    /// Local end time including pauses, RFC 3339 with offset
+    pub end: String,
+    /// Focus time, excluding pauses
+    pub duration_secs: u32,
+    /// Length the timer was set to, for sessions timed by tomatocrab
+    pub planned_secs: Option<u32>,
+    pub paused_secs: u32,
+    pub completed: bool,
+    /// Logged with `tomatocrab log` or imported rather than timed
+    pub manual: bool,
+    /// Planned task the session counted toward
+    pub task_id: Option<Uuid>,
+    /// Self-rated focus from 1 to 5
+    pub focus: Option<u8>,
+    pub accomplished: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl SessionRecord {
+    /// Field names in serialization order, the CSV header even when there are no rows
+    pub const FIELDS: [&'static str; 15] = [
+        "id",
+        "task",
+        "tags",
+        "date",
+        "start",
+        "end",
+        "duration_secs",
+        "planned_secs",
+        "paused_secs",
+        "completed",
+        "manual",
+        "task_id",
+        "focus",
+        "accomplished",
+        "notes",
+    ];
+}
+
+impl From<&Session> for SessionRecord {
+    fn from(session: &Session) -> Self {
+        Self {
+            id: session.id,
+            task: session.task.clone(),
+            tags: session.tags(),
+            date: session.started_at.with_timezone(&Local).date_naive(),
+            start: format_timestamp(session.started_at),
+            end: format_timestamp(session.ended_at()),
+            duration_secs: session.duration_secs,
+            planned_secs: session.planned_secs,
+            paused_secs: session.paused_secs(),
+            completed: session.completed,
+            manual: session.manual,
+            task_id: session.task_id,
+            focus: session.focus,
+            accomplished: session.accomplished.clone(),
+            notes: session.notes.clone(),
+        }
+    }
+}
+
+/// Statistics for one period as written by `stats --output`
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsRecord {
+    /// Description of the period, e.g. "This Week"
+    pub period: String,
+    /// First and last day of the period, or null for all time
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub sessions: usize,
+    pub completed: usize,
+    pub interrupted: usize,
+    pub manual: usize,
+    /// Percentage of sessions completed, to one decimal place
+    pub completion_rate: f64,
+    pub focus_secs: u32,
+    pub average_duration_secs: u32,
+    /// Mean self-rated focus, to one decimal place
+    pub average_focus: Option<f64>,
+    /// Streaks of days meeting the daily goal, only for the current period
+    pub current_streak: Option<u32>,
+    pub longest_streak: Option<u32>,
+}
+
+impl StatsRecord {
+    /// Field names in serialization order, the CSV header
+    pub const FIELDS: [&'static str; 13] = [
+        "period",
+        "from",
+        "to",
+        "sessions",
+        "completed",
+        "interrupted",
+        "manual",
+        "completion_rate",
+        "focus_secs",
+        "average_duration_secs",
+        "average_focus",
+        "current_streak",
+        "longest_streak",
+    ];
+
+    pub fn new(stats: &SessionStats, period: String, range: Option<(NaiveDate, NaiveDate)>) -> Self {
+        Self {
+            period,
+            from: range.map(|(from, _)| from),
+            to: range.map(|(_, to)| to),
+            sessions: stats.total_sessions,
+            completed: stats.completed_sessions,
+            interrupted: stats.interrupted_sessions,
+            manual: stats.manual_sessions,
+            completion_rate: round_tenth(stats.completion_rate()),
+            focus_secs: stats.total_focus_time_secs,
+            average_duration_secs: stats.average_duration_secs,
+            average_focus: stats.average_focus.map(round_tenth),
+            current_streak: stats.streaks.map(|streaks| streaks.current),
+            longest_streak: stats.streaks.map(|streaks| streaks.longest),
+        }
+    }
+}
+
+/// Statistics for a period, and the period before it with `--compare`
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsReport {
+    #[serde(flatten)]
+    pub current: StatsRecord,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<StatsRecord>,
+}
+
+/// Write sessions as a JSON or YAML list, or CSV rows
+pub fn write_sessions<W: Write>(sessions: &[&Session], format: OutputFormat, writer: W) -> Result<()> {
+    let records: Vec<SessionRecord> = sessions.iter().map(|session| SessionRecord::from(*session)).collect();
+    match format {
+        OutputFormat::Csv => write_csv(&SessionRecord::FIELDS, &records, writer),
+        _ => write_document(&records, format, writer),
+    }
+}
+
+/// Write statistics as a JSON or YAML object, or CSV with one row per period
+pub fn write_stats<W: Write>(report: &StatsReport, format: OutputFormat, writer: W) -> Result<()> {
+    match format {
+        OutputFormat::Csv => {
+            let rows: Vec<&StatsRecord> = std::iter::once(&report.current).chain(&report.previous).collect();
+            write_csv(&StatsRecord::FIELDS, &rows, writer)
+        }
+        _ => write_document(report, format, writer),
+    }
+}
+
+/// Write a JSON or YAML document
+fn write_document<W: Write>(value: &impl Serialize, format: OutputFormat, mut writer: W) -> Result<()> {
+    match format {
+        OutputFormat::Yaml => serde_yaml::to_writer(&mut writer, value).wrap_err("Failed to write YAML")?,
+        _ => {
+            serde_json::to_writer_pretty(&mut writer, value).wrap_err("Failed to write JSON")?;
+            writeln!(writer)?;
+        }
+    }
+    writer.flush().wrap_err("Failed to flush output")
+}
+
+/// Write records as CSV under `header`, which is written even without any records
+fn write_csv<W: Write>(header: &[&str], records: &[impl Serialize], writer: W) -> Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer
+        .write_record(header)
+        .wrap_err("Failed to write CSV header")?;
+    for record in records {
+        let Value::Object(fields) = serde_json::to_value(record)? else {
+            continue;
+        };
+        debug_assert!(fields.keys().eq(header.iter().copied()), "CSV header out of date");
+        csv_writer
+            .write_record(fields.values().map(csv_field))
+            .wrap_err("Failed to write CSV row")?;
+    }
+    csv_writer.flush().wrap_err("Failed to flush CSV output")
+}
+
+/// Flat text for a CSV cell: empty for null, lists joined by spaces
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(csv_field).collect::<Vec<_>>().join(" "),
+        other => other.to_string(),
+    }
+}
+
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Display sessions in CLI format
+pub fn display_sessions(filtered: &[&Session], label: &str) {
+    if filtered.is_empty() {
+        println!("No sessions found.");
+        return;
+    }
+
+    println!("Sessions ({})", label);
+    println!("{}", "=".repeat(60));
+    println!("{:<12} {:<8} {:<24} {:<10} Status", "Date", "Time", "Task", "Duration");
+    println!("{}", "-".repeat(60));
+
+    for session in filtered.iter().rev() {
+        print_session_line(session);
+    }
+}
+
+/// Display sessions in CLI format under a heading for each day or week, newest first
+pub fn display_grouped_sessions(filtered: &[&Session], label: &str, period: ReportPeriod) {
+    if filtered.is_empty() {
+        println!("No sessions found.");
+        return;
+    }
+
+    println!("Sessions ({})", label);
+    println!("{}", "=".repeat(60));
+    println!("{:<12} {:<8} {:<24} {:<10} Status", "Date", "Time", "Task", "Duration");
+
+    let mut groups: Vec<(NaiveDate, Vec<&Session>)> = Vec::new();
+    for session in filtered.iter().rev() {
+        let start = period.start_of(session.started_at.with_timezone(&Local).date_naive());
+        match groups.iter_mut().find(|(date, _)| *date == start) {
+            Some((_, sessions)) => sessions.push(session),
+            None => groups.push((start, vec![session])),
+        }
+    }
+    groups.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+
+    for (start, sessions) in groups {
+        let stats = SessionStats::from_sessions(sessions.iter().copied());
+        println!();
+        println!("{}", period.label(start));
+        println!("  {}", stats.subtotal());
+        println!("{}", "-".repeat(60));
+        for session in sessions {
+            print_session_line(session);
+        }
+    }
+}
+
+/// Print one session as a row of the CLI session table
+fn print_session_line(session: &Session) {
+    let local_time = session.started_at.with_timezone(&Local);
+    let date = local_time.format("%Y-%m-%d").to_string();
+    let time = local_time.format("%H:%M").to_string();
+    let duration = format_duration(session.duration_secs);
+    let status = match (session.completed, session.manual) {
+        (true, false) => "Completed",
+        (false, false) => "Interrupted",
+        (true, true) => "Completed (manual)",
+        (false, true) => "Interrupted (manual)",
+    };
+    let task = pad(&session.task, 24);
+
+    println!("{:<12} {:<8} {} {:<10} {}", date, time, task, duration, status);
+}
+
+/// Display statistics in CLI format
+pub fn display_stats(stats: &SessionStats) {
+    println!("Session Statistics");
+    println!("==================");
+    println!("Total Sessions:      {}", stats.total_sessions);
+    println!("Completed:           {} ({:.1}%)", stats.completed_sessions, stats.completion_rate());
+    println!("Interrupted:         {}", stats.interrupted_sessions);
+    println!("Manually Logged:     {}", stats.manual_sessions);
+    println!("Total Focus Time:    {}", format_duration_long(stats.total_focus_time_secs));
+    println!("Average Duration:    {}", format_duration_long(stats.average_duration_secs));
+    if let Some(focus) = stats.average_focus {
+        println!("Average Focus:       {:.1} / 5", focus);
+    }
+    if let Some(streaks) = stats.streaks {
+        println!("Current Streak:      {}", format_days(streaks.current));
+        println!("Longest Streak:      {}", format_days(streaks.longest));
+    }
+}
+
+/// Display a period's statistics next to the period before, with the change in each
+pub fn display_comparison(comparison: &StatsComparison, current_label: &str, previous_label: &str) {
+    println!("Comparison");
+    println!("==========");
+    println!("{:<18} {:>24} {:>24}   Change", "", current_label, previous_label);
+
+    let (current, previous) = (&comparison.current, &comparison.previous);
+    let sessions = comparison.sessions();
+    println!(
+        "{:<18} {:>24} {:>24}   {} {:+} ({})",
+        "Sessions",
+        current.total_sessions,
+        previous.total_sessions,
+        sessions.arrow(),
+        sessions.change,
+        sessions.percent_label()
+    );
+    let rate = comparison.completion_rate();
+    println!(
+        "{:<18} {:>24} {:>24}   {} {:+.1} pts ({})",
+        "Completion Rate",
+        format!("{:.1}%", current.completion_rate()),
+        format!("{:.1}%", previous.completion_rate()),
+        rate.arrow(),
+        rate.change,
+        rate.percent_label()
+    );
+    for (label, delta, now, before) in [
+        (
+            "Focus Time",
+            comparison.focus_time(),
+            current.total_focus_time_secs,
+            previous.total_focus_time_secs,
+        ),
+        (
+            "Average Duration",
+            comparison.average_duration(),
+            current.average_duration_secs,
+            previous.average_duration_secs,
+        ),
+    ] {
+        let sign = if delta.change < 0.0 { "-" } else { "+" };
+        println!(
+            "{:<18} {:>24} {:>24}   {} {}{} ({})",
+            label,
+            format_duration_long(now),
+            format_duration_long(before),
+            delta.arrow(),
+            sign,
+            format_duration_long(now.abs_diff(before)),
+            delta.percent_label()
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Command palette: run any command by name, with arguments
+//!
+//! The first word of the input is fuzzy-matched against command names and the rest is
+//! passed to the command, e.g. `filter month` or `extend 5`.
+
+use crate::keymap::{Command, Context, COMMANDS};
+use crate::suggest::fuzzy_score;
+
+/// A command that can be run from the palette
+#[derive(Debug, Clone, Copy)]
+pub enum PaletteCommand {
+    /// A command that can also be bound to a key
+    Bound(&'static Command),
+    Filter,
+    Profile,
+    Export,
+    Extend,
+    View,
+}
+
+/// Commands that take arguments, listed before the bindable ones
+const WITH_ARGUMENTS: [PaletteCommand; 5] = [
+    PaletteCommand::Filter,
+    PaletteCommand::Profile,
+    PaletteCommand::Export,
+    PaletteCommand::Extend,
+    PaletteCommand::View,
+];
+
+impl PaletteCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaletteCommand::Bound(command) => command.name,
+            PaletteCommand::Filter => "filter",
+            PaletteCommand::Profile => "profile",
+            PaletteCommand::Export => "export",
+            PaletteCommand::Extend => "extend",
+            PaletteCommand::View => "view",
+        }
+    }
+
+    /// Arguments the command expects, for display
+    pub fn usage(&self) -> &'static str {
+        match self {
+            PaletteCommand::Bound(_) => "",
+            PaletteCommand::Filter => "today|week|month|year|all",
+            PaletteCommand::Profile => "NAME",
+            PaletteCommand::Export => "csv|json|jsonl|ics [PATH]",
+            PaletteCommand::Extend => "MINUTES",
+            PaletteCommand::View => "timer|plan|history|stats",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PaletteCommand::Bound(command) => command.description,
+            PaletteCommand::Filter => "Show sessions from a period in History and Stats",
+            PaletteCommand::Profile => "Switch to timer lengths from a profile in config.toml",
+            PaletteCommand::Export => "Export the filtered sessions to a file",
+            PaletteCommand::Extend => "Add time to the running timer",
+            PaletteCommand::View => "Switch to a view",
+        }
+    }
+}
+
+/// Split palette input into the command word and its arguments
+pub fn split(input: &str) -> (&str, &str) {
+    let input = input.trim_start();
+    match input.split_once(char::is_whitespace) {
+        Some((word, args)) => (word, args.trim()),
+        None => (input, ""),
+    }
+}
+
+/// Commands matching the first word of `input`, best first
+///
+/// Where views share a command name (e.g. `delete`), the one for the `context` view is
+/// offered.
+pub fn matches(input: &str, context: Context) -> Vec<PaletteCommand> {
+    let mut commands: Vec<PaletteCommand> = Vec::new();
+    for command in WITH_ARGUMENTS
+        .into_iter()
+        .chain(COMMANDS.iter().map(PaletteCommand::Bound))
+    {
+        match commands.iter_mut().find(|existing| existing.name() == command.name()) {
+            Some(existing) => {
+                if matches!(command, PaletteCommand::Bound(bound) if bound.context == context) {
+                    *existing = command;
+                }
+            }
+            None => commands.push(command),
+        }
+    }
+
+    let (query, _) = split(input);
+    let mut scored: Vec<(i64, PaletteCommand)> = commands
+        .into_iter()
+        .filter_map(|command| fuzzy_score(query, command.name()).map(|score| (score, command)))
+        .collect();
+    // An exact name always comes first; otherwise keep list order among equal scores
+    scored.sort_by_key(|(score, command)| (command.name() != query, std::cmp::Reverse(*score)));
+    scored.into_iter().map(|(_, command)| command).collect()
+}
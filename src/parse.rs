@@ -0,0 +1,140 @@
+//! Parsers for human-friendly command line arguments
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// Parse a duration such as `25m`, `1h30m`, `90s` or a bare number of minutes
+///
+/// Returns the duration in seconds.
+pub fn parse_duration(input: &str) -> Result<u32, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("duration cannot be empty".to_string());
+    }
+
+    // A bare number is interpreted as minutes, matching the timer flags
+    if let Ok(minutes) = input.parse::<u32>() {
+        return minutes
+            .checked_mul(60)
+            .ok_or_else(|| format!("duration '{}' is too large", input));
+    }
+
+    let mut total: u32 = 0;
+    let mut number = String::new();
+    for c in input.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' | 's' => {
+                let value: u32 = number
+                    .parse()
+                    .map_err(|_| format!("invalid duration '{}'", input))?;
+                let multiplier = match c {
+                    'h' => 3600,
+                    'm' => 60,
+                    _ => 1,
+                };
+                total = value
+                    .checked_mul(multiplier)
+                    .and_then(|secs| total.checked_add(secs))
+                    .ok_or_else(|| format!("duration '{}' is too large", input))?;
+                number.clear();
+            }
+            ' ' => {}
+            _ => return Err(format!("invalid duration '{}' (expected e.g. 25m, 1h30m, 90s)", input)),
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(format!("invalid duration '{}': missing unit after {}", input, number));
+    }
+    if total == 0 {
+        return Err("duration must be greater than zero".to_string());
+    }
+
+    Ok(total)
+}
+
+/// Parse a local start time such as `14:00` (today) or `2024-05-01 14:00`
+pub fn parse_start_time(input: &str) -> Result<DateTime<Utc>, String> {
+    match NaiveTime::parse_from_str(input.trim(), "%H:%M") {
+        Ok(time) => local_to_utc(Local::now().date_naive().and_time(time)),
+        Err(_) => parse_timestamp(input),
+    }
+}
+
+/// Parse an RFC 3339 timestamp or a local `YYYY-MM-DD HH:MM[:SS]` time
+pub fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .ok_or_else(|| format!("invalid time '{}' (expected HH:MM or YYYY-MM-DD HH:MM)", input))?;
+
+    local_to_utc(naive)
+}
+
+/// Parse a clock-style duration such as `0:25:00` or `25:00`
+///
+/// Returns the duration in seconds.
+pub fn parse_clock_duration(input: &str) -> Result<u32, String> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|part| part.parse::<u32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("invalid duration '{}' (expected HH:MM:SS)", input))?;
+
+    let (hours, minutes, seconds) = match numbers.as_slice() {
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        [minutes, seconds] => (0, *minutes, *seconds),
+        _ => return Err(format!("invalid duration '{}' (expected HH:MM:SS)", input)),
+    };
+    hours
+        .checked_mul(3600)
+        .zip(minutes.checked_mul(60))
+        .and_then(|(hours, minutes)| hours.checked_add(minutes)?.checked_add(seconds))
+        .ok_or_else(|| format!("duration '{}' is too large", input))
+}
+
+/// Parse a calendar date in `YYYY-MM-DD` form
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}' (expected YYYY-MM-DD)", input))
+}
+
+/// Parse `today`, `yesterday` or a `YYYY-MM-DD` date, relative to `today`
+pub fn parse_day(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    match input.trim().to_lowercase().as_str() {
+        "today" => Ok(today),
+        "yesterday" => Ok(today - chrono::Duration::days(1)),
+        _ => parse_date(input),
+    }
+}
+
+/// Split a trailing pomodoro estimate such as `~3` off a task description
+///
+/// Returns the description without the estimate, and the estimate if there was one.
+pub fn split_estimate(input: &str) -> (&str, Option<u32>) {
+    let input = input.trim();
+    if let Some((task, last)) = input.rsplit_once(' ')
+        && let Some(estimate) = last.strip_prefix('~').and_then(|n| n.parse::<u32>().ok())
+        && estimate > 0
+        && !task.trim().is_empty()
+    {
+        return (task.trim_end(), Some(estimate));
+    }
+    (input, None)
+}
+
+/// Convert a naive local time to UTC, rejecting times skipped or repeated by DST changes
+fn local_to_utc(naive: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .map(|local| local.with_timezone(&Utc))
+        .ok_or_else(|| format!("'{}' is not a valid local time", naive))
+}
@@ -0,0 +1,73 @@
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A task planned for a particular day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanTask {
+    /// Unique identifier, referenced by the sessions worked on this task
+    pub id: Uuid,
+    /// What the task is
+    pub title: String,
+    /// Estimated number of pomodoros
+    pub estimate: Option<u32>,
+    /// Whether the task has been completed
+    pub done: bool,
+    /// Day the task is planned for
+    pub date: NaiveDate,
+}
+
+impl PlanTask {
+    /// Create a new task planned for `date`
+    pub fn new(title: String, date: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            title,
+            estimate: None,
+            done: false,
+            date,
+        }
+    }
+}
+
+/// The ordered list of planned tasks across days
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Plan {
+    pub tasks: Vec<PlanTask>,
+}
+
+impl Plan {
+    /// Indices into `tasks` of the tasks shown for `today`, in plan order
+    ///
+    /// Unfinished tasks from earlier days roll over until they are completed
+    /// or explicitly carried over.
+    pub fn indices_for(&self, today: NaiveDate) -> Vec<usize> {
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| task.date == today || (task.date < today && !task.done))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Add a task for today at the end of the plan, returning its id
+    pub fn add(&mut self, title: String, estimate: Option<u32>) -> Uuid {
+        let mut task = PlanTask::new(title, Local::now().date_naive());
+        task.estimate = estimate;
+        let id = task.id;
+        self.tasks.push(task);
+        id
+    }
+
+    /// Swap two tasks in plan order
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.tasks.swap(a, b);
+    }
+
+    /// Move a task to tomorrow
+    pub fn carry_over(&mut self, index: usize) {
+        if let Some(task) = self.tasks.get_mut(index) {
+            task.date = Local::now().date_naive() + Duration::days(1);
+        }
+    }
+}
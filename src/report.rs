@@ -0,0 +1,366 @@
+//! Timesheet reports rendered as Org, Markdown or HTML
+
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use clap::ValueEnum;
+
+use crate::components::session_list::SessionStats;
+use crate::session::Session;
+
+/// Width of the longest bar in the ASCII chart
+const CHART_WIDTH: usize = 40;
+
+/// Output format for `tomatocrab report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Org-mode with CLOCK lines for org-agenda clocktables
+    Org,
+    Markdown,
+    Html,
+}
+
+/// Length of each period in the breakdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportPeriod {
+    Day,
+    Week,
+}
+
+impl ReportPeriod {
+    /// First day of the period containing `date` (weeks start on Monday)
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            ReportPeriod::Day => date,
+            ReportPeriod::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    /// Heading for the period starting on `start`
+    pub fn label(&self, start: NaiveDate) -> String {
+        match self {
+            ReportPeriod::Day => start.format("%Y-%m-%d %A").to_string(),
+            ReportPeriod::Week => format!(
+                "Week {} ({} to {})",
+                start.iso_week().week(),
+                start,
+                start + Duration::days(6)
+            ),
+        }
+    }
+}
+
+/// How sessions are grouped within a period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportGrouping {
+    Task,
+    /// By first `#tag`, so each session is only counted once
+    Tag,
+}
+
+/// Sessions sharing a task or tag within a period
+struct Group<'a> {
+    name: String,
+    sessions: Vec<&'a Session>,
+}
+
+/// One day or week of the report
+struct Period<'a> {
+    /// First day of the period
+    start: NaiveDate,
+    sessions: Vec<&'a Session>,
+    groups: Vec<Group<'a>>,
+}
+
+/// A report ready to be rendered
+pub struct Report<'a> {
+    title: String,
+    period: ReportPeriod,
+    grouping: ReportGrouping,
+    sessions: Vec<&'a Session>,
+    periods: Vec<Period<'a>>,
+}
+
+impl<'a> Report<'a> {
+    /// Build a report from already filtered sessions
+    pub fn new(title: String, sessions: Vec<&'a Session>, period: ReportPeriod, grouping: ReportGrouping) -> Self {
+        let mut periods: Vec<Period<'a>> = Vec::new();
+        let mut sorted = sessions.clone();
+        sorted.sort_by_key(|session| session.started_at);
+
+        for session in sorted {
+            let start = period.start_of(local_date(session));
+            match periods.iter_mut().find(|p| p.start == start) {
+                Some(existing) => existing.sessions.push(session),
+                None => periods.push(Period {
+                    start,
+                    sessions: vec![session],
+                    groups: Vec::new(),
+                }),
+            }
+        }
+
+        for period in &mut periods {
+            for session in &period.sessions {
+                let name = group_name(session, grouping);
+                match period.groups.iter_mut().find(|g| g.name == name) {
+                    Some(group) => group.sessions.push(session),
+                    None => period.groups.push(Group {
+                        name,
+                        sessions: vec![session],
+                    }),
+                }
+            }
+            period
+                .groups
+                .sort_by_key(|group| std::cmp::Reverse(focus_secs(&group.sessions)));
+        }
+
+        Self {
+            title,
+            period,
+            grouping,
+            sessions,
+            periods,
+        }
+    }
+
+    /// Render the report in the requested format
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Org => self.render_org(),
+            ReportFormat::Markdown => self.render_markdown(),
+            ReportFormat::Html => self.render_html(),
+        }
+    }
+
+    fn render_org(&self) -> String {
+        let mut out = String::new();
+        let totals = SessionStats::from_sessions(self.sessions.iter().copied());
+
+        let _ = writeln!(out, "#+TITLE: Pomodoro report ({})", self.title);
+        let _ = writeln!(out, "#+STARTUP: overview");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Total: {}", summary_line(&totals));
+        let _ = writeln!(out);
+
+        for period in &self.periods {
+            let stats = SessionStats::from_sessions(period.sessions.iter().copied());
+            let _ = writeln!(out, "* {} :: {}", self.period.label(period.start), summary_line(&stats));
+            for group in &period.groups {
+                let stats = SessionStats::from_sessions(group.sessions.iter().copied());
+                let _ = writeln!(out, "** {} :: {}", group.name, summary_line(&stats));
+                let _ = writeln!(out, "   :LOGBOOK:");
+                for session in &group.sessions {
+                    let start = session.started_at.with_timezone(&Local);
+                    let end = session.ended_at().with_timezone(&Local);
+                    let minutes = session.duration_secs / 60;
+                    let _ = writeln!(
+                        out,
+                        "   CLOCK: [{}]--[{}] => {:>2}:{:02}",
+                        start.format("%Y-%m-%d %a %H:%M"),
+                        end.format("%Y-%m-%d %a %H:%M"),
+                        minutes / 60,
+                        minutes % 60
+                    );
+                }
+                let _ = writeln!(out, "   :END:");
+            }
+        }
+
+        let _ = writeln!(out, "* Chart");
+        let _ = writeln!(out, "#+begin_example");
+        out.push_str(&self.ascii_chart());
+        let _ = writeln!(out, "#+end_example");
+        out
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut out = String::new();
+        let totals = SessionStats::from_sessions(self.sessions.iter().copied());
+
+        let _ = writeln!(out, "# Pomodoro report ({})", self.title);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "**Total:** {}", summary_line(&totals));
+
+        for period in &self.periods {
+            let stats = SessionStats::from_sessions(period.sessions.iter().copied());
+            let _ = writeln!(out);
+            let _ = writeln!(out, "## {}", self.period.label(period.start));
+            let _ = writeln!(out);
+            let _ = writeln!(out, "| {} | Sessions | Completed | Focus |", self.group_heading());
+            let _ = writeln!(out, "|---|---:|---:|---:|");
+            for group in &period.groups {
+                let stats = SessionStats::from_sessions(group.sessions.iter().copied());
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {:.0}% | {} |",
+                    group.name.replace('|', "\\|"),
+                    stats.total_sessions,
+                    stats.completion_rate(),
+                    format_hours_minutes(stats.total_focus_time_secs)
+                );
+            }
+            let _ = writeln!(
+                out,
+                "| **Total** | **{}** | **{:.0}%** | **{}** |",
+                stats.total_sessions,
+                stats.completion_rate(),
+                format_hours_minutes(stats.total_focus_time_secs)
+            );
+        }
+
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## Chart");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "```");
+        out.push_str(&self.ascii_chart());
+        let _ = writeln!(out, "```");
+        out
+    }
+
+    fn render_html(&self) -> String {
+        let mut out = String::new();
+        let totals = SessionStats::from_sessions(self.sessions.iter().copied());
+        let title = format!("Pomodoro report ({})", self.title);
+
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(out, "<html>\n<head>\n<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{}</title>", escape_html(&title));
+        let _ = writeln!(
+            out,
+            "<style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:2px 8px}}td.num{{text-align:right}}</style>"
+        );
+        let _ = writeln!(out, "</head>\n<body>");
+        let _ = writeln!(out, "<h1>{}</h1>", escape_html(&title));
+        let _ = writeln!(out, "<p><strong>Total:</strong> {}</p>", escape_html(&summary_line(&totals)));
+
+        for period in &self.periods {
+            let stats = SessionStats::from_sessions(period.sessions.iter().copied());
+            let _ = writeln!(out, "<h2>{}</h2>", escape_html(&self.period.label(period.start)));
+            let _ = writeln!(out, "<table>");
+            let _ = writeln!(
+                out,
+                "<tr><th>{}</th><th>Sessions</th><th>Completed</th><th>Focus</th></tr>",
+                self.group_heading()
+            );
+            for group in &period.groups {
+                let stats = SessionStats::from_sessions(group.sessions.iter().copied());
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.0}%</td><td class=\"num\">{}</td></tr>",
+                    escape_html(&group.name),
+                    stats.total_sessions,
+                    stats.completion_rate(),
+                    format_hours_minutes(stats.total_focus_time_secs)
+                );
+            }
+            let _ = writeln!(
+                out,
+                "<tr><th>Total</th><th>{}</th><th>{:.0}%</th><th>{}</th></tr>",
+                stats.total_sessions,
+                stats.completion_rate(),
+                format_hours_minutes(stats.total_focus_time_secs)
+            );
+            let _ = writeln!(out, "</table>");
+        }
+
+        let _ = writeln!(out, "<h2>Chart</h2>");
+        let _ = writeln!(out, "<pre>{}</pre>", escape_html(&self.ascii_chart()));
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+
+    /// Horizontal bar chart of focus time per period
+    fn ascii_chart(&self) -> String {
+        let totals: Vec<(String, u32)> = self
+            .periods
+            .iter()
+            .map(|period| (self.short_label(period.start), focus_secs(&period.sessions)))
+            .collect();
+        let max = totals.iter().map(|(_, secs)| *secs).max().unwrap_or(0).max(1);
+        let label_width = totals.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for (label, secs) in &totals {
+            let width = (*secs as usize * CHART_WIDTH).div_ceil(max as usize);
+            let _ = writeln!(
+                out,
+                "{:<label_width$} | {:<CHART_WIDTH$} {}",
+                label,
+                "#".repeat(width),
+                format_hours_minutes(*secs)
+            );
+        }
+        out
+    }
+
+    /// Column heading for the grouping
+    fn group_heading(&self) -> &'static str {
+        match self.grouping {
+            ReportGrouping::Task => "Task",
+            ReportGrouping::Tag => "Tag",
+        }
+    }
+
+    /// Compact label for chart rows
+    fn short_label(&self, start: NaiveDate) -> String {
+        match self.period {
+            ReportPeriod::Day => start.format("%a %m-%d").to_string(),
+            ReportPeriod::Week => format!("W{:02} {}", start.iso_week().week(), start.format("%m-%d")),
+        }
+    }
+}
+
+/// Local calendar date a session started on
+fn local_date(session: &Session) -> NaiveDate {
+    session.started_at.with_timezone(&Local).date_naive()
+}
+
+/// Name of the group a session belongs to
+fn group_name(session: &Session, grouping: ReportGrouping) -> String {
+    match grouping {
+        ReportGrouping::Task => session.task.trim().to_string(),
+        ReportGrouping::Tag => session
+            .tags()
+            .into_iter()
+            .next()
+            .map(|tag| format!("#{}", tag))
+            .unwrap_or_else(|| "(untagged)".to_string()),
+    }
+}
+
+/// Total focus time of some sessions
+fn focus_secs(sessions: &[&Session]) -> u32 {
+    sessions.iter().map(|session| session.duration_secs).sum()
+}
+
+/// One-line summary of session totals
+fn summary_line(stats: &SessionStats) -> String {
+    format!(
+        "{} {}, {:.0}% completed, {} focus",
+        stats.total_sessions,
+        if stats.total_sessions == 1 { "session" } else { "sessions" },
+        stats.completion_rate(),
+        format_hours_minutes(stats.total_focus_time_secs)
+    )
+}
+
+/// Format seconds as `1h 05m` or `25m`
+pub fn format_hours_minutes(secs: u32) -> String {
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Escape text for inclusion in HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
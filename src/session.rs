@@ -1,7 +1,14 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// A stretch of time a work session's timer was paused
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pause {
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: u32,
+}
+
 /// Represents a completed or interrupted Pomodoro session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
@@ -15,6 +22,27 @@ pub struct Session {
     pub duration_secs: u32,
     /// Whether the session ran its full intended duration
     pub completed: bool,
+    /// Whether the session was logged by hand rather than timed
+    #[serde(default)]
+    pub manual: bool,
+    /// Planned task this session was worked on
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    /// What got done, from the retrospective after the session
+    #[serde(default)]
+    pub accomplished: Option<String>,
+    /// Self-rated focus from 1 (distracted) to 5 (deep focus)
+    #[serde(default)]
+    pub focus: Option<u8>,
+    /// Free-form notes about the session
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Length the timer was set to, when timed
+    #[serde(default)]
+    pub planned_secs: Option<u32>,
+    /// Times the timer was paused during the session
+    #[serde(default)]
+    pub pauses: Vec<Pause>,
 }
 
 impl Session {
@@ -26,6 +54,93 @@ impl Session {
             started_at,
             duration_secs,
             completed,
+            manual: false,
+            task_id: None,
+            accomplished: None,
+            focus: None,
+            notes: None,
+            planned_secs: None,
+            pauses: Vec::new(),
+        }
+    }
+
+    /// Create a session that was entered manually (e.g. via `tomatocrab log`)
+    pub fn manual(task: String, started_at: DateTime<Utc>, duration_secs: u32, completed: bool) -> Self {
+        Self {
+            manual: true,
+            ..Self::new(task, started_at, duration_secs, completed)
+        }
+    }
+
+    /// When the session ended, including any time spent paused
+    pub fn ended_at(&self) -> DateTime<Utc> {
+        self.started_at + Duration::seconds((self.duration_secs + self.paused_secs()) as i64)
+    }
+
+    /// Spans of time spent focusing, skipping any pauses
+    pub fn focus_intervals(&self) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let mut pauses: Vec<&Pause> = self.pauses.iter().collect();
+        pauses.sort_by_key(|pause| pause.started_at);
+
+        let mut intervals = Vec::new();
+        let mut cursor = self.started_at;
+        let mut remaining = self.duration_secs as i64;
+        for pause in pauses {
+            let focused = (pause.started_at - cursor).num_seconds().clamp(0, remaining);
+            if focused > 0 {
+                intervals.push((cursor, cursor + Duration::seconds(focused)));
+            }
+            remaining -= focused;
+            cursor = cursor.max(pause.started_at) + Duration::seconds(pause.duration_secs as i64);
         }
+        if remaining > 0 {
+            intervals.push((cursor, cursor + Duration::seconds(remaining)));
+        }
+        intervals
+    }
+
+    /// Total time spent paused
+    pub fn paused_secs(&self) -> u32 {
+        self.pauses.iter().map(|pause| pause.duration_secs).sum()
+    }
+
+    /// Tags attached to the task as `#hashtags`, lowercased and without the `#`
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        let words = self.task.split_whitespace().filter_map(|word| word.strip_prefix('#'));
+        for word in words {
+            let tag = word
+                .trim_end_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+                .to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// Whether every word of `query` appears in the task, tags, notes or retrospective
+    pub fn matches_search(&self, query: &str) -> bool {
+        let haystack = [
+            Some(self.task.as_str()),
+            self.notes.as_deref(),
+            self.accomplished.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.tags().iter().map(String::as_str).collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+
+        query
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| haystack.contains(word))
+    }
+
+    /// Whether this session's time span overlaps another session's
+    pub fn overlaps(&self, other: &Session) -> bool {
+        self.started_at < other.ended_at() && other.started_at < self.ended_at()
     }
 }
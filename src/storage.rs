@@ -3,12 +3,15 @@ use std::path::PathBuf;
 
 use color_eyre::eyre::{Context, Result};
 use directories::ProjectDirs;
+use uuid::Uuid;
 
+use crate::plan::Plan;
 use crate::session::Session;
 
 /// Manages persistence of sessions to disk
 pub struct Storage {
     data_path: PathBuf,
+    plan_path: PathBuf,
 }
 
 impl Storage {
@@ -21,8 +24,12 @@ impl Storage {
         fs::create_dir_all(data_dir).wrap_err("Failed to create data directory")?;
 
         let data_path = data_dir.join("sessions.json");
+        let plan_path = data_dir.join("plan.json");
 
-        Ok(Self { data_path })
+        Ok(Self {
+            data_path,
+            plan_path,
+        })
     }
 
     /// Load all sessions from disk
@@ -45,18 +52,77 @@ impl Storage {
 
     /// Save a session to disk
     pub fn save_session(&self, session: Session) -> Result<()> {
+        self.append_sessions(vec![session])
+    }
+
+    /// Append several sessions to disk in a single write
+    ///
+    /// Sessions are kept ordered by start time, so back-dated entries
+    /// (manual logs, imports) land in the right place.
+    pub fn append_sessions(&self, new_sessions: Vec<Session>) -> Result<()> {
+        let mut sessions = self.load_sessions()?;
+        sessions.extend(new_sessions);
+        sessions.sort_by_key(|session| session.started_at);
+        self.save_all(&sessions)
+    }
+
+    /// Apply `update` to the stored session with this id, returning whether it was found
+    pub fn update_session(&self, id: Uuid, update: impl FnOnce(&mut Session)) -> Result<bool> {
+        let mut sessions = self.load_sessions()?;
+        let Some(session) = sessions.iter_mut().find(|session| session.id == id) else {
+            return Ok(false);
+        };
+        update(session);
+        self.save_all(&sessions)?;
+        Ok(true)
+    }
+
+    /// Remove the stored session with this id, returning whether it was found
+    pub fn delete_session(&self, id: Uuid) -> Result<bool> {
         let mut sessions = self.load_sessions()?;
-        sessions.push(session);
+        let before = sessions.len();
+        sessions.retain(|session| session.id != id);
+        if sessions.len() == before {
+            return Ok(false);
+        }
+        self.save_all(&sessions)?;
+        Ok(true)
+    }
 
-        let content = serde_json::to_string_pretty(&sessions).wrap_err("Failed to serialize sessions")?;
+    /// Replace all stored sessions
+    pub fn save_all(&self, sessions: &[Session]) -> Result<()> {
+        let content = serde_json::to_string_pretty(sessions).wrap_err("Failed to serialize sessions")?;
 
         fs::write(&self.data_path, content).wrap_err("Failed to write sessions file")?;
 
         Ok(())
     }
 
+    /// Load the daily plan from disk
+    pub fn load_plan(&self) -> Result<Plan> {
+        if !self.plan_path.exists() {
+            return Ok(Plan::default());
+        }
+
+        let content = fs::read_to_string(&self.plan_path).wrap_err("Failed to read plan file")?;
+
+        if content.trim().is_empty() {
+            return Ok(Plan::default());
+        }
+
+        serde_json::from_str(&content).wrap_err("Failed to parse plan file")
+    }
+
+    /// Save the daily plan to disk
+    pub fn save_plan(&self, plan: &Plan) -> Result<()> {
+        let content = serde_json::to_string_pretty(plan).wrap_err("Failed to serialize plan")?;
+
+        fs::write(&self.plan_path, content).wrap_err("Failed to write plan file")?;
+
+        Ok(())
+    }
+
     /// Get the path where sessions are stored
-    #[allow(dead_code)]
     pub fn data_path(&self) -> &PathBuf {
         &self.data_path
     }
@@ -0,0 +1,103 @@
+//! Task autocomplete ranked by fuzzy match, recency and frequency
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::session::Session;
+
+/// Days after which a past session counts half as much toward a task's rank
+const HALF_LIFE_DAYS: f64 = 7.0;
+
+/// A task worked on in past sessions
+#[derive(Debug, Clone)]
+pub struct PastTask {
+    /// Most recent spelling of the task
+    pub text: String,
+    /// When the task was last started
+    pub last_used: DateTime<Utc>,
+    /// Number of sessions, each weighted by how recent it is
+    pub frecency: f64,
+}
+
+/// Distinct tasks from past sessions, most recently used first
+///
+/// Tasks differing only in case or surrounding whitespace are merged.
+pub fn past_tasks(sessions: &[Session], now: DateTime<Utc>) -> Vec<PastTask> {
+    let mut tasks: HashMap<String, PastTask> = HashMap::new();
+
+    for session in sessions {
+        let text = session.task.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let age_days = (now - session.started_at).num_seconds().max(0) as f64 / 86_400.0;
+        let weight = 0.5_f64.powf(age_days / HALF_LIFE_DAYS);
+
+        let entry = tasks.entry(text.to_lowercase()).or_insert_with(|| PastTask {
+            text: text.to_string(),
+            last_used: session.started_at,
+            frecency: 0.0,
+        });
+        entry.frecency += weight;
+        if session.started_at >= entry.last_used {
+            entry.text = text.to_string();
+            entry.last_used = session.started_at;
+        }
+    }
+
+    let mut tasks: Vec<PastTask> = tasks.into_values().collect();
+    tasks.sort_by_key(|task| std::cmp::Reverse(task.last_used));
+    tasks
+}
+
+/// Score how well `query` fuzzily matches `candidate`, or `None` if it does not
+///
+/// Every query character must appear in order. Matches at the start of words,
+/// runs of consecutive characters and prefixes score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.trim().to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for (i, c) in candidate.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *c != query[next] {
+            continue;
+        }
+
+        score += 1;
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(p) if p + 1 == i => score += 5,
+            Some(p) => score -= ((i - p - 1) as i64).min(5),
+            None => score -= (i as i64).min(10),
+        }
+        previous = Some(i);
+        next += 1;
+    }
+
+    if next < query.len() {
+        return None;
+    }
+    if candidate.starts_with(&query) {
+        score += 20;
+    }
+    Some(score)
+}
+
+/// Overall rank of a candidate combining match quality and usage
+pub fn rank_score(match_score: i64, frecency: f64) -> f64 {
+    match_score as f64 + 10.0 * frecency.ln_1p()
+}
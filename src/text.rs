@@ -0,0 +1,39 @@
+//! Display-width aware helpers for fitting text into columns
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Marker appended to truncated text
+const ELLIPSIS: &str = "...";
+
+/// Shorten `text` to at most `max_width` terminal columns, ending in `...` if cut
+///
+/// Text is only cut between grapheme clusters, so multi-byte characters, emoji
+/// and combining marks are never split.
+pub fn truncate(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    // Too narrow for the ellipsis, so just cut
+    let ellipsis = if max_width > ELLIPSIS.len() { ELLIPSIS } else { "" };
+    let budget = max_width - ellipsis.len();
+    let mut width = 0;
+    let mut out = String::new();
+    for grapheme in text.graphemes(true) {
+        width += grapheme.width();
+        if width > budget {
+            break;
+        }
+        out.push_str(grapheme);
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+/// Truncate and left-align `text` in a column `width` terminal columns wide
+pub fn pad(text: &str, width: usize) -> String {
+    let text = truncate(text, width);
+    let padding = width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(padding))
+}
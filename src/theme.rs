@@ -1,182 +1,577 @@
-//! Centralized color palette and style helpers for the TUI
+//! Color themes and style helpers for the TUI
 //!
-//! Tomato-inspired color scheme for a polished Pomodoro experience.
+//! The theme is picked once at startup from a built-in preset or a user theme file,
+//! adapted to the colors the terminal can show, and read through [`Theme::current`].
 
-use ratatui::style::{Color, Modifier, Style};
-
-/// Primary tomato red color
-pub const PRIMARY: Color = Color::Rgb(231, 76, 60);
-
-/// Accent gold color
-pub const ACCENT: Color = Color::Rgb(243, 156, 18);
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
 
-/// Success green color
-pub const SUCCESS: Color = Color::Rgb(39, 174, 96);
-
-/// Warning orange color
-pub const WARNING: Color = Color::Rgb(230, 126, 34);
+use color_eyre::eyre::{bail, Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
+
+/// Names of the built-in themes
+pub const PRESETS: [&str; 4] = ["dark", "light", "solarized", "high-contrast"];
+
+/// Shade characters standing in for heatmap colors when colors are off
+const SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+static CURRENT: OnceLock<Theme> = OnceLock::new();
+
+/// Colors used throughout the TUI
+///
+/// Theme files use the same keys, with colors written as names (`red`, `light-blue`),
+/// hex (`#e74c3c`) or 256-color indexes (`208`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// Titles, active tabs and progress
+    pub primary: Color,
+    /// Headers and subtitles
+    pub accent: Color,
+    pub success: Color,
+    pub warning: Color,
+    /// Background of gauges and empty heatmap cells
+    pub surface: Color,
+    pub text_muted: Color,
+    pub text_bright: Color,
+    pub border: Color,
+    /// Key hints and focused inputs
+    pub highlight: Color,
+    /// Background of the selected row
+    pub selection: Color,
+    pub timer_running: Color,
+    pub timer_paused: Color,
+    pub timer_finished: Color,
+    pub timer_idle: Color,
+    pub timer_break: Color,
+    pub timer_long_break: Color,
+    /// Heatmap cell colors, from no focus to well past the daily goal
+    pub heatmap: [Color; 5],
+    /// Set when colors are off, so selections fall back to reversed text
+    #[serde(skip)]
+    pub monochrome: bool,
+}
 
-/// Background dark color
-pub const BG_DARK: Color = Color::Rgb(30, 30, 30);
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
 
-/// Surface color for cards/panels
-pub const SURFACE: Color = Color::Rgb(45, 45, 45);
+impl Theme {
+    /// Tomato-inspired colors tuned for dark terminals
+    pub fn dark() -> Self {
+        let success = Color::Rgb(39, 174, 96);
+        let accent = Color::Rgb(243, 156, 18);
+        let surface = Color::Rgb(45, 45, 45);
+        let text_muted = Color::Rgb(127, 140, 141);
+        Self {
+            primary: Color::Rgb(231, 76, 60),
+            accent,
+            success,
+            warning: Color::Rgb(230, 126, 34),
+            surface,
+            text_muted,
+            text_bright: Color::Rgb(236, 240, 241),
+            border: Color::Rgb(52, 73, 94),
+            highlight: Color::Rgb(52, 152, 219),
+            selection: Color::Rgb(60, 60, 60),
+            timer_running: success,
+            timer_paused: accent,
+            timer_finished: Color::Cyan,
+            timer_idle: text_muted,
+            timer_break: Color::Rgb(26, 188, 156),
+            timer_long_break: Color::Rgb(52, 152, 219),
+            heatmap: [
+                surface,
+                Color::Rgb(14, 68, 41),
+                Color::Rgb(0, 109, 50),
+                Color::Rgb(38, 166, 65),
+                Color::Rgb(57, 211, 83),
+            ],
+            monochrome: false,
+        }
+    }
 
-/// Muted text color
-pub const TEXT_MUTED: Color = Color::Rgb(127, 140, 141);
+    /// Darker variants of the same colors, readable on light backgrounds
+    pub fn light() -> Self {
+        let success = Color::Rgb(30, 132, 73);
+        let accent = Color::Rgb(175, 96, 0);
+        let surface = Color::Rgb(226, 228, 230);
+        let text_muted = Color::Rgb(96, 106, 108);
+        Self {
+            primary: Color::Rgb(192, 57, 43),
+            accent,
+            success,
+            warning: Color::Rgb(186, 74, 0),
+            surface,
+            text_muted,
+            text_bright: Color::Rgb(28, 30, 33),
+            border: Color::Rgb(150, 162, 174),
+            highlight: Color::Rgb(31, 97, 141),
+            selection: Color::Rgb(208, 215, 222),
+            timer_running: success,
+            timer_paused: accent,
+            timer_finished: Color::Rgb(0, 128, 128),
+            timer_idle: text_muted,
+            timer_break: Color::Rgb(17, 122, 101),
+            timer_long_break: Color::Rgb(31, 97, 141),
+            heatmap: [
+                surface,
+                Color::Rgb(155, 233, 168),
+                Color::Rgb(64, 196, 99),
+                Color::Rgb(48, 161, 78),
+                Color::Rgb(33, 110, 57),
+            ],
+            monochrome: false,
+        }
+    }
 
-/// Bright text color
-pub const TEXT_BRIGHT: Color = Color::Rgb(236, 240, 241);
+    /// Solarized dark
+    pub fn solarized() -> Self {
+        let base02 = Color::Rgb(7, 54, 66);
+        let base01 = Color::Rgb(88, 110, 117);
+        let yellow = Color::Rgb(181, 137, 0);
+        let blue = Color::Rgb(38, 139, 210);
+        let cyan = Color::Rgb(42, 161, 152);
+        let green = Color::Rgb(133, 153, 0);
+        Self {
+            primary: Color::Rgb(220, 50, 47),
+            accent: yellow,
+            success: green,
+            warning: Color::Rgb(203, 75, 22),
+            surface: base02,
+            text_muted: base01,
+            text_bright: Color::Rgb(147, 161, 161),
+            border: base01,
+            highlight: blue,
+            selection: base02,
+            timer_running: green,
+            timer_paused: yellow,
+            timer_finished: cyan,
+            timer_idle: base01,
+            timer_break: cyan,
+            timer_long_break: blue,
+            heatmap: [
+                base02,
+                Color::Rgb(62, 72, 0),
+                Color::Rgb(98, 113, 0),
+                green,
+                Color::Rgb(172, 198, 0),
+            ],
+            monochrome: false,
+        }
+    }
 
-/// Border color
-pub const BORDER: Color = Color::Rgb(52, 73, 94);
+    /// Bright ANSI colors on the terminal's own background
+    pub fn high_contrast() -> Self {
+        Self {
+            primary: Color::LightRed,
+            accent: Color::LightYellow,
+            success: Color::LightGreen,
+            warning: Color::Yellow,
+            surface: Color::DarkGray,
+            text_muted: Color::Gray,
+            text_bright: Color::White,
+            border: Color::White,
+            highlight: Color::LightCyan,
+            selection: Color::Blue,
+            timer_running: Color::LightGreen,
+            timer_paused: Color::LightYellow,
+            timer_finished: Color::LightCyan,
+            timer_idle: Color::Gray,
+            timer_break: Color::LightCyan,
+            timer_long_break: Color::LightBlue,
+            heatmap: [
+                Color::DarkGray,
+                Color::Green,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::White,
+            ],
+            monochrome: false,
+        }
+    }
 
-/// Highlight/selection color
-pub const HIGHLIGHT: Color = Color::Rgb(52, 152, 219);
+    /// Built-in theme by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
 
-/// Timer running color
-pub const TIMER_RUNNING: Color = SUCCESS;
+    /// Theme named by `spec`: a preset, a file in `themes_dir`, or a path to a theme file
+    pub fn load(spec: &str, themes_dir: Option<&Path>) -> Result<Self> {
+        if let Some(theme) = Self::preset(spec) {
+            return Ok(theme);
+        }
+        if let Some(path) = themes_dir.map(|dir| dir.join(format!("{}.toml", spec)))
+            && path.exists()
+        {
+            return Self::from_file(&path);
+        }
+        let path = Path::new(spec);
+        if path.exists() {
+            return Self::from_file(path);
+        }
+        bail!(
+            "Unknown theme '{}' (expected one of {} or a theme file)",
+            spec,
+            PRESETS.join(", ")
+        )
+    }
 
-/// Timer paused color
-pub const TIMER_PAUSED: Color = ACCENT;
+    /// Read a theme file, filling colors it leaves out from the preset named by `extends`
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+        let mut table: toml::Table =
+            toml::from_str(&contents).wrap_err_with(|| format!("Invalid theme file {}", path.display()))?;
+
+        let base = match table.remove("extends") {
+            Some(toml::Value::String(name)) => Self::preset(&name).ok_or_else(|| {
+                color_eyre::eyre::eyre!("{}: unknown preset '{}' in extends", path.display(), name)
+            })?,
+            Some(_) => bail!("{}: extends must be a preset name", path.display()),
+            None => Self::dark(),
+        };
+        let mut merged = toml::Table::try_from(&base).wrap_err("Failed to read the base theme")?;
+        merged.extend(table);
+        merged
+            .try_into()
+            .wrap_err_with(|| format!("Invalid theme file {}", path.display()))
+    }
 
-/// Timer finished color
-pub const TIMER_FINISHED: Color = Color::Cyan;
+    /// Convert every color to one the terminal can show
+    pub fn adapt(mut self, support: ColorSupport) -> Self {
+        let heatmap = self.heatmap;
+        for color in self.colors_mut() {
+            *color = support.convert(*color);
+        }
+        // Close shades of one hue can collapse into the same ANSI color
+        let distinct = (1..self.heatmap.len()).all(|i| !self.heatmap[..i].contains(&self.heatmap[i]));
+        if support == ColorSupport::Ansi16 && !distinct && heatmap != self.heatmap {
+            self.heatmap = Self::high_contrast().heatmap;
+        }
+        self.monochrome = support == ColorSupport::None;
+        self
+    }
 
-/// Timer idle color
-pub const TIMER_IDLE: Color = TEXT_MUTED;
+    fn colors_mut(&mut self) -> impl Iterator<Item = &mut Color> {
+        [
+            &mut self.primary,
+            &mut self.accent,
+            &mut self.success,
+            &mut self.warning,
+            &mut self.surface,
+            &mut self.text_muted,
+            &mut self.text_bright,
+            &mut self.border,
+            &mut self.highlight,
+            &mut self.selection,
+            &mut self.timer_running,
+            &mut self.timer_paused,
+            &mut self.timer_finished,
+            &mut self.timer_idle,
+            &mut self.timer_break,
+            &mut self.timer_long_break,
+        ]
+        .into_iter()
+        .chain(self.heatmap.iter_mut())
+    }
 
-/// Timer short break color (teal)
-pub const TIMER_BREAK: Color = Color::Rgb(26, 188, 156);
+    /// Make this the theme returned by [`Theme::current`]; only the first call has an effect
+    pub fn install(self) {
+        let _ = CURRENT.set(self);
+    }
 
-/// Timer long break color (blue)
-pub const TIMER_LONG_BREAK: Color = Color::Rgb(52, 152, 219);
+    /// The installed theme, or the dark preset if none was installed
+    pub fn current() -> &'static Theme {
+        CURRENT.get_or_init(Theme::dark)
+    }
 
-/// Style helpers for consistent UI styling
-pub struct Theme;
+}
 
+/// Style helpers for consistent UI styling, in the installed theme's colors
 impl Theme {
     /// Title style (bold primary)
     pub fn title() -> Style {
-        Style::default().fg(PRIMARY).add_modifier(Modifier::BOLD)
+        Style::default().fg(Self::current().primary).add_modifier(Modifier::BOLD)
     }
 
     /// Subtitle style (accent)
     pub fn subtitle() -> Style {
-        Style::default().fg(ACCENT)
+        Style::default().fg(Self::current().accent)
     }
 
     /// Muted text style
     pub fn muted() -> Style {
-        Style::default().fg(TEXT_MUTED)
+        Style::default().fg(Self::current().text_muted)
     }
 
     /// Bright text style
     pub fn bright() -> Style {
-        Style::default().fg(TEXT_BRIGHT)
+        Style::default().fg(Self::current().text_bright)
     }
 
     /// Success style (green)
     pub fn success() -> Style {
-        Style::default().fg(SUCCESS)
+        Style::default().fg(Self::current().success)
     }
 
     /// Warning style (orange)
     pub fn warning() -> Style {
-        Style::default().fg(WARNING)
+        Style::default().fg(Self::current().warning)
     }
 
     /// Border style
     pub fn border() -> Style {
-        Style::default().fg(BORDER)
+        Style::default().fg(Self::current().border)
     }
 
     /// Active border style
     pub fn border_active() -> Style {
-        Style::default().fg(PRIMARY)
+        Style::default().fg(Self::current().primary)
     }
 
     /// Highlight style for selections
     pub fn highlight() -> Style {
-        Style::default().fg(HIGHLIGHT).add_modifier(Modifier::BOLD)
+        Style::default().fg(Self::current().highlight).add_modifier(Modifier::BOLD)
     }
 
     /// Key hint style (for keyboard shortcuts)
     pub fn key_hint() -> Style {
-        Style::default().fg(HIGHLIGHT).add_modifier(Modifier::BOLD)
+        Style::default().fg(Self::current().highlight).add_modifier(Modifier::BOLD)
     }
 
     /// Key action style (description of what key does)
     pub fn key_action() -> Style {
-        Style::default().fg(TEXT_MUTED)
+        Style::default().fg(Self::current().text_muted)
     }
 
     /// Tab active style
     pub fn tab_active() -> Style {
         Style::default()
-            .fg(PRIMARY)
+            .fg(Self::current().primary)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Tab inactive style
     pub fn tab_inactive() -> Style {
-        Style::default().fg(TEXT_MUTED)
+        Style::default().fg(Self::current().text_muted)
     }
 
     /// Stat card value style
     pub fn stat_value() -> Style {
         Style::default()
-            .fg(TEXT_BRIGHT)
+            .fg(Self::current().text_bright)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Stat card label style
     pub fn stat_label() -> Style {
-        Style::default().fg(TEXT_MUTED)
+        Style::default().fg(Self::current().text_muted)
     }
 
     /// Progress gauge style
     pub fn progress_gauge() -> Style {
-        Style::default().fg(PRIMARY).bg(SURFACE)
+        Style::default().fg(Self::current().primary).bg(Self::current().surface)
     }
 
     /// Table header style
     pub fn table_header() -> Style {
         Style::default()
-            .fg(ACCENT)
+            .fg(Self::current().accent)
             .add_modifier(Modifier::BOLD)
     }
 
     /// Table row normal style
     pub fn table_row() -> Style {
-        Style::default().fg(TEXT_BRIGHT)
+        Style::default().fg(Self::current().text_bright)
     }
 
     /// Table row selected style
     pub fn table_row_selected() -> Style {
-        Style::default()
-            .fg(TEXT_BRIGHT)
-            .bg(Color::Rgb(60, 60, 60))
-            .add_modifier(Modifier::BOLD)
+        Self::selected(
+            Style::default()
+                .fg(Self::current().text_bright)
+                .add_modifier(Modifier::BOLD),
+        )
+    }
+
+    /// Add the selection background to `style`, or reverse it when colors are off
+    pub fn selected(style: Style) -> Style {
+        let theme = Self::current();
+        if theme.monochrome {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style.bg(theme.selection)
+        }
     }
 
     /// Session completed status style
     pub fn status_completed() -> Style {
-        Style::default().fg(SUCCESS)
+        Style::default().fg(Self::current().success)
     }
 
     /// Session interrupted status style
     pub fn status_interrupted() -> Style {
-        Style::default().fg(WARNING)
+        Style::default().fg(Self::current().warning)
     }
 
     /// Sparkline style
     pub fn sparkline() -> Style {
-        Style::default().fg(SUCCESS)
+        Style::default().fg(Self::current().success)
     }
 
     /// Bar chart style
     pub fn bar_chart() -> Style {
-        Style::default().fg(PRIMARY)
+        Style::default().fg(Self::current().primary)
+    }
+
+    /// Heatmap cell style for a focus level (0-4)
+    pub fn heatmap(level: u8) -> Style {
+        Style::default().fg(Self::current().heatmap[level.min(4) as usize])
+    }
+
+    /// Heatmap cell symbol for a focus level, shaded by level when colors are off
+    pub fn heatmap_cell(level: u8) -> &'static str {
+        if Self::current().monochrome {
+            SHADES[level.min(4) as usize]
+        } else {
+            "■"
+        }
     }
 }
+
+/// How many colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// Colors are off, e.g. because `NO_COLOR` is set
+    None,
+    /// The 16 ANSI colors
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit RGB
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Work out color support from `NO_COLOR`, `COLORTERM` and `TERM`
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        if var("NO_COLOR").is_some() {
+            return ColorSupport::None;
+        }
+        if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+            return ColorSupport::TrueColor;
+        }
+        match var("TERM") {
+            Some(term) if term == "dumb" => ColorSupport::None,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => ColorSupport::TrueColor,
+            Some(term) if term.contains("256") => ColorSupport::Ansi256,
+            Some(_) => ColorSupport::Ansi16,
+            // Windows terminals don't set TERM and handle RGB
+            None if cfg!(windows) => ColorSupport::TrueColor,
+            None => ColorSupport::Ansi16,
+        }
+    }
+
+    /// The closest color to `color` this terminal can show
+    pub fn convert(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorSupport::None, _) => Color::Reset,
+            (ColorSupport::TrueColor, _) => color,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorSupport::Ansi16, Color::Indexed(index)) if index >= 16 => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_16(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// Levels of each channel in the 6x6x6 color cube of the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colors with their usual xterm values
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Index of the closest color in the cube or grayscale ramp of the 256-color palette
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |channel: u8| {
+        CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| level.abs_diff(channel))
+            .map_or(0, |(i, _)| i as u8)
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if distance((r, g, b), indexed_rgb(gray)) < distance((r, g, b), indexed_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// The closest of the 16 ANSI colors
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// RGB value of a 256-color palette index
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..16 => ANSI_16[index as usize].1,
+        16..232 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Squared distance between two colors
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
@@ -3,30 +3,45 @@ use std::time::Duration;
 
 use color_eyre::eyre::Result;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+        KeyEventKind, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 
 use crate::action::Action;
+use crate::keymap::Key;
 
 /// Terminal wrapper that handles setup and cleanup
 pub struct Tui {
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Whether clicks and scrolling are captured, which stops the terminal selecting text
+    mouse: bool,
 }
 
 impl Tui {
     /// Create and initialize a new terminal
     pub fn new() -> Result<Self> {
         let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-        Ok(Self { terminal })
+        Ok(Self { terminal, mouse: true })
+    }
+
+    /// Whether to capture the mouse; without it the terminal can select and copy text
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
     }
 
     /// Enter the TUI mode
     pub fn enter(&mut self) -> Result<()> {
         enable_raw_mode()?;
-        execute!(stdout(), EnterAlternateScreen)?;
+        execute!(stdout(), EnterAlternateScreen, EnableBracketedPaste)?;
+        if self.mouse {
+            execute!(stdout(), EnableMouseCapture)?;
+        }
         self.terminal.hide_cursor()?;
         self.terminal.clear()?;
         Ok(())
@@ -35,7 +50,10 @@ impl Tui {
     /// Exit the TUI mode and restore terminal
     pub fn exit(&mut self) -> Result<()> {
         self.terminal.show_cursor()?;
-        execute!(stdout(), LeaveAlternateScreen)?;
+        if self.mouse {
+            execute!(stdout(), DisableMouseCapture)?;
+        }
+        execute!(stdout(), DisableBracketedPaste, LeaveAlternateScreen)?;
         disable_raw_mode()?;
         Ok(())
     }
@@ -51,39 +69,32 @@ impl Tui {
 
     /// Poll for events with a timeout
     pub fn poll_event(&self, timeout: Duration) -> Result<Option<Action>> {
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                // Only handle key press events
-                if key.kind != KeyEventKind::Press {
-                    return Ok(Some(Action::None));
-                }
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
 
-                let action = match key.code {
-                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        Action::Quit
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Paste(text) => return Ok(Some(Action::Paste(text))),
+            // Movement and releases arrive constantly while the mouse is captured
+            Event::Mouse(mouse) => {
+                return Ok(match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                        Some(Action::Mouse(mouse))
                     }
-                    KeyCode::Enter => Action::Confirm,
-                    KeyCode::Esc => Action::Cancel,
-                    KeyCode::Backspace => Action::Backspace,
-                    KeyCode::Tab => {
-                        if key.modifiers.contains(KeyModifiers::SHIFT) {
-                            Action::PrevTab
-                        } else {
-                            Action::NextTab
-                        }
-                    }
-                    KeyCode::Right => Action::NextTab,
-                    KeyCode::Left => Action::PrevTab,
-                    KeyCode::Up => Action::ScrollUp,
-                    KeyCode::Down => Action::ScrollDown,
-                    KeyCode::Char(c) => Action::Input(c), // All chars handled by app state
-                    _ => Action::None,
-                };
-
-                return Ok(Some(action));
+                    _ => None,
+                });
             }
+            _ => return Ok(None),
+        };
+
+        // Only handle key press events
+        if key.kind != KeyEventKind::Press {
+            return Ok(Some(Action::None));
         }
-        Ok(None)
+
+        // Keys are resolved by the app, which knows whether text is being typed
+        Ok(Some(Action::Key(Key::from(key))))
     }
 }
 